@@ -0,0 +1,244 @@
+//!
+//! Blocking IPP client
+//!
+use std::{io::Read, time::Duration};
+
+use futures::Future;
+use log::debug;
+use reqwest::{Body, Client, RedirectPolicy, StatusCode};
+
+use ipp_proto::{
+    ipp::{self, IppVersion},
+    operation::IppOperation,
+    parser::IppParseResult,
+    request::IppRequestResponse,
+    IppAttributes, IppHeader,
+};
+
+use crate::{
+    http::{check_status, http_error, http_url, parse_certs, parse_identity, printer_uri, redirect_uri, secure_uri},
+    IppClientBuilder, IppError,
+};
+
+/// Blocking IPP client which sends requests on the calling thread, for applications without
+/// a tokio runtime. Created with `IppClientBuilder::build_blocking`.
+///
+/// The request is streamed from `IppRequestResponse::into_reader`, so a document payload must be
+/// readable without a runtime, for example one opened with `IppJobSource::open_std`. Redirects,
+/// TLS upgrade, Unix sockets, the in-flight limit and the response size limits of the asynchronous
+/// client are not supported.
+pub struct IppClient {
+    uri: String,
+    client: Client,
+    basic_auth: Option<(String, String)>,
+    version: Option<IppVersion>,
+}
+
+impl IppClient {
+    pub(crate) fn from_builder(builder: IppClientBuilder) -> Result<IppClient, IppError> {
+        // Some printers don't support gzip
+        let mut http = Client::builder()
+            .gzip(false)
+            .connect_timeout(builder.connect_timeout)
+            .redirect(RedirectPolicy::none());
+
+        if !builder.verify_hostname {
+            debug!("Disabling hostname verification!");
+            http = http.danger_accept_invalid_hostnames(true);
+        }
+
+        if !builder.verify_certificate {
+            debug!("Disabling certificate verification!");
+            http = http.danger_accept_invalid_certs(true);
+        }
+
+        // the blocking client has a default timeout, zero disables it like for the asynchronous client
+        http = http.timeout(if builder.timeout > 0 {
+            Some(Duration::from_secs(builder.timeout))
+        } else {
+            None
+        });
+
+        if builder.tcp_nodelay {
+            debug!("Enabling TCP_NODELAY");
+            http = http.tcp_nodelay();
+        }
+
+        for ca_cert in parse_certs(builder.ca_certs).wait()? {
+            http = http.add_root_certificate(ca_cert);
+        }
+        if let Some(identity) = parse_identity(builder.client_identity).wait()? {
+            debug!("Using client identity");
+            http = http.identity(identity);
+        }
+
+        Ok(IppClient {
+            uri: builder.uri,
+            client: http.build()?,
+            basic_auth: builder.basic_auth,
+            version: builder.version,
+        })
+    }
+
+    /// send IPP operation
+    pub fn send<T>(&self, operation: T) -> Result<IppAttributes, IppError>
+    where
+        T: IppOperation,
+    {
+        debug!("Sending IPP operation");
+        let mut request = operation.into_ipp_request(&printer_uri(&self.uri));
+        if let Some(version) = self.version {
+            request.header_mut().version = version;
+        }
+        self.send_request(request)
+            .and_then(check_status)
+            .map(|resp| resp.attributes().clone())
+    }
+
+    /// Send request and return response.
+    ///
+    /// Like the asynchronous client, a request without a document is repeated once with the version
+    /// reported by a printer which does not support the requested one.
+    pub fn send_request(&self, request: IppRequestResponse) -> Result<IppRequestResponse, IppError> {
+        if request.payload().is_some() {
+            return self.send_http(request);
+        }
+
+        let header = request.header().clone();
+        let attributes = request.attributes().clone();
+
+        let resp = self.send_http(request)?;
+        let supported = resp.header().version;
+        if resp.header().operation_status != ipp::StatusCode::ServerErrorVersionNotSupported as u16
+            || supported == header.version
+        {
+            return Ok(resp);
+        }
+        debug!("{:?} not supported, retrying with {:?}", header.version, supported);
+        self.send_http(IppRequestResponse::from_parse_result(IppParseResult {
+            header: IppHeader::new(supported, header.operation_status, header.request_id),
+            attributes,
+            payload: None,
+            raw: None,
+            payload_offset: 0,
+        }))
+    }
+
+    fn send_http(&self, request: IppRequestResponse) -> Result<IppRequestResponse, IppError> {
+        let url = http_url(&self.uri)?;
+        let body = match request.content_length() {
+            Some(length) => {
+                debug!("Setting Content-Length to {}", length);
+                Body::sized(request.into_reader(), length)
+            }
+            None => Body::new(request.into_reader()),
+        };
+
+        let mut builder = self
+            .client
+            .post(url.clone())
+            .header("Content-Type", "application/ipp")
+            .body(body);
+
+        let has_credentials = self.basic_auth.is_some() || !url.username().is_empty();
+        if let Some((ref username, ref password)) = self.basic_auth {
+            debug!("Setting basic auth: {} ****", username);
+            builder = builder.basic_auth(username, Some(password));
+        } else if !url.username().is_empty() {
+            debug!("Setting basic auth: {} ****", url.username());
+            builder = builder.basic_auth(
+                url.username(),
+                url.password()
+                    .map(|p| percent_encoding::percent_decode(p.as_bytes()).decode_utf8().unwrap()),
+            );
+        }
+
+        let mut response = builder.send().map_err(http_error)?;
+        if has_credentials && response.status() == StatusCode::UNAUTHORIZED {
+            debug!("Credentials rejected by the server");
+            return Err(IppError::AuthenticationFailed);
+        }
+        if response.status().is_redirection() {
+            if let Some(location) = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|l| l.to_str().ok())
+                .and_then(|l| redirect_uri(&self.uri, l))
+            {
+                debug!("Request redirected to {}", location);
+                return Err(IppError::Redirected(location));
+            }
+        }
+        if response.status() == StatusCode::UPGRADE_REQUIRED {
+            if let Some(secure) = secure_uri(&self.uri) {
+                debug!("Printer requires TLS");
+                return Err(IppError::TlsRequired(secure));
+            }
+        }
+        response = response.error_for_status()?;
+
+        let mut data = Vec::new();
+        response.read_to_end(&mut data)?;
+        Ok(IppRequestResponse::from_bytes(&data)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use ipp_proto::{
+        attribute::{IppAttribute, PRINTER_STATE},
+        ipp::{DelimiterTag, PrinterState},
+        model::Compression,
+        IppJobSource, IppOperationBuilder, IppValue,
+    };
+    use ipp_server::testing::MockServer;
+
+    use super::*;
+
+    // printer answering every request with the given IPP status and printer state
+    fn printer(status: ipp::StatusCode) -> MockServer {
+        let mut attributes = IppAttributes::new();
+        attributes.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE, IppValue::Enum(PrinterState::Idle as i32)),
+        );
+        MockServer::start(status, attributes).unwrap()
+    }
+
+    #[test]
+    fn test_blocking_send() {
+        let server = printer(ipp::StatusCode::SuccessfulOK);
+        let client = IppClientBuilder::new(&server.uri()).build_blocking().unwrap();
+        let attrs = client
+            .send(IppOperationBuilder::get_printer_attributes().build())
+            .unwrap();
+        assert_eq!(attrs.printer_state(), Some(PrinterState::Idle));
+
+        let server = printer(ipp::StatusCode::ClientErrorNotFound);
+        let client = IppClientBuilder::new(&server.uri()).build_blocking().unwrap();
+        assert!(matches!(
+            client.send(IppOperationBuilder::get_printer_attributes().build()),
+            Err(IppError::StatusError(ipp::StatusCode::ClientErrorNotFound))
+        ));
+    }
+
+    #[test]
+    fn test_blocking_print_job() {
+        let server = printer(ipp::StatusCode::SuccessfulOK);
+        let client = IppClientBuilder::new(&server.uri()).build_blocking().unwrap();
+
+        let path = std::env::temp_dir().join(format!("ipp-client-blocking-{}.bin", std::process::id()));
+        std::fs::write(&path, [b'x'; 100_000]).unwrap();
+        let source = IppJobSource::open_std(&path).unwrap();
+        assert_eq!(source.length(), Some(100_000));
+        client.send(IppOperationBuilder::print_job(source).build()).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        // compressed documents are sent with chunked encoding
+        let source = IppJobSource::from_std(io::Cursor::new(vec![b'x'; 100_000])).compress(Compression::Gzip);
+        client.send(IppOperationBuilder::print_job(source).build()).unwrap();
+        assert_eq!(server.requests().len(), 2);
+    }
+}
@@ -0,0 +1,171 @@
+//!
+//! HTTP helpers shared by the asynchronous and blocking clients
+//!
+use std::{fs, io, path::PathBuf};
+
+use futures::Future;
+use num_traits::FromPrimitive;
+use reqwest::{Certificate, Identity};
+use url::Url;
+
+use ipp_proto::{ipp, request::IppRequestResponse};
+
+use crate::IppError;
+
+// HTTP URL for an IPP URI, with the default port of the ipp and ipps schemes
+pub(crate) fn http_url(uri: &str) -> Result<Url, IppError> {
+    let mut url = Url::parse(uri).map_err(|e| IppError::ParamError(e.to_string()))?;
+    match url.scheme() {
+        "ipp" => {
+            url.set_scheme("http").unwrap();
+            if url.port().is_none() {
+                url.set_port(Some(631)).unwrap();
+            }
+        }
+        "ipps" => {
+            url.set_scheme("https").unwrap();
+            if url.port().is_none() {
+                url.set_port(Some(443)).unwrap();
+            }
+        }
+        _ => {}
+    }
+    Ok(url)
+}
+
+/// Compute the `printer-uri` attribute value from the URI used to reach the printer.
+///
+/// The `http` and `https` schemes are replaced with `ipp` and `ipps`, the port is kept explicit
+/// unless it is the IPP default port 631, and credentials are removed, for example
+/// `http://user@host:631/printers/foo` becomes `ipp://host/printers/foo`.
+/// Other URIs are returned without credentials
+pub fn printer_uri(uri: &str) -> String {
+    let mut url = match Url::parse(uri) {
+        Ok(url) => url,
+        Err(_) => return uri.to_owned(),
+    };
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+
+    let scheme = match url.scheme() {
+        "http" => "ipp",
+        "https" => "ipps",
+        _ => return url.to_string(),
+    };
+    let port = url.port_or_known_default();
+    if url.set_scheme(scheme).is_ok() {
+        let _ = url.set_port(port.filter(|&port| port != 631));
+    }
+    url.to_string()
+}
+
+pub(crate) fn parse_certs(certs: Vec<PathBuf>) -> impl Future<Item = Vec<Certificate>, Error = IppError> {
+    futures::lazy(move || {
+        let mut result = Vec::new();
+
+        for cert_file in certs {
+            let buf = match fs::read(&cert_file) {
+                Ok(buf) => buf,
+                Err(e) => return Err(IppError::from(e)),
+            };
+            let ca_cert = match Certificate::from_der(&buf).or_else(|_| Certificate::from_pem(&buf)) {
+                Ok(ca_cert) => ca_cert,
+                Err(e) => return Err(IppError::from(e)),
+            };
+            result.push(ca_cert);
+        }
+        Ok(result)
+    })
+}
+
+// PKCS#12 client identity, errors are reported as TLS errors naming the file
+pub(crate) fn parse_identity(
+    identity: Option<(PathBuf, String)>,
+) -> impl Future<Item = Option<Identity>, Error = IppError> {
+    futures::lazy(move || match identity {
+        Some((path, password)) => fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|buf| Identity::from_pkcs12_der(&buf, &password).map_err(|e| e.to_string()))
+            .map(Some)
+            .map_err(|e| IppError::TlsError(format!("{}: {}", path.display(), e))),
+        None => Ok(None),
+    })
+}
+
+pub(crate) fn check_status(resp: IppRequestResponse) -> Result<IppRequestResponse, IppError> {
+    // unknown codes in the successful range are not errors either
+    let status = resp.header().operation_status;
+    if status <= 0x00ff {
+        Ok(resp)
+    } else {
+        // IPP error
+        Err(IppError::StatusError(
+            ipp::StatusCode::from_u16(status).unwrap_or(ipp::StatusCode::ServerErrorInternalError),
+        ))
+    }
+}
+
+// resolve redirect location against the request URI, keeping credentials for the same host
+pub(crate) fn redirect_uri(uri: &str, location: &str) -> Option<String> {
+    let base = Url::parse(uri).ok()?;
+    let mut target = base.join(location).ok()?;
+    if target.host_str() == base.host_str() && target.username().is_empty() && !base.username().is_empty() {
+        let _ = target.set_username(base.username());
+        let _ = target.set_password(base.password());
+    }
+    Some(target.to_string())
+}
+
+// secure counterpart of a plain ipp or http URI, keeping the IPP port
+pub(crate) fn secure_uri(uri: &str) -> Option<String> {
+    let mut url = Url::parse(uri).ok()?;
+    match url.scheme() {
+        "ipp" => {
+            if url.port().is_none() {
+                url.set_port(Some(631)).ok()?;
+            }
+            url.set_scheme("ipps").ok()?;
+        }
+        "http" => url.set_scheme("https").ok()?,
+        _ => return None,
+    }
+    Some(url.to_string())
+}
+
+// the connection could not be established, so the request did not reach the printer
+pub(crate) fn is_connect_error(error: &reqwest::Error) -> bool {
+    error
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<hyper::Error>())
+        .is_some_and(hyper::Error::is_connect)
+}
+
+// connect timeouts are reported by the connector, the overall timeout by the response future
+fn is_connect_timeout(error: &reqwest::Error) -> bool {
+    error.is_timeout() && is_connect_error(error)
+}
+
+// TLS failures are wrapped into I/O errors by the connector
+fn tls_error(error: &reqwest::Error) -> Option<String> {
+    let mut source = error.get_ref().map(|e| e as &(dyn std::error::Error + 'static));
+    while let Some(e) = source {
+        let tls = e
+            .downcast_ref::<native_tls::Error>()
+            .or_else(|| e.downcast_ref::<io::Error>()?.get_ref()?.downcast_ref());
+        if let Some(tls) = tls {
+            return Some(tls.to_string());
+        }
+        source = e.source();
+    }
+    None
+}
+
+pub(crate) fn http_error(error: reqwest::Error) -> IppError {
+    if is_connect_timeout(&error) {
+        IppError::ConnectTimeout
+    } else if let Some(e) = tls_error(&error) {
+        IppError::TlsError(e)
+    } else {
+        IppError::HttpError(error)
+    }
+}
@@ -1,13 +1,27 @@
 use std::{
     fmt, io,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
-use ipp_proto::{ipp::StatusCode, ParseError};
+use ipp_proto::{
+    ipp::{IppVersion, StatusCode},
+    ParseError, TypeMismatch,
+};
 
-pub use crate::client::IppClient;
+#[cfg(feature = "async")]
+pub use crate::client::{IppClient, PrintOptions, PrintTextOptions, RetryPolicy, SendOptions};
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "async")]
 pub mod client;
+#[cfg(any(feature = "async", feature = "blocking"))]
+mod http;
+#[cfg(all(test, feature = "async"))]
+mod testing;
+#[cfg(all(unix, feature = "async"))]
+mod unix;
 
 /// IPP error
 #[derive(Debug)]
@@ -28,8 +42,38 @@ pub enum IppError {
     ParseError(ParseError),
     /// Missing attribute in response
     MissingAttribute,
-    /// Invalid attribute type
-    InvalidAttributeType,
+    /// Attribute in response has an unexpected type
+    InvalidAttributeType(TypeMismatch),
+    /// Server redirected the request to the given location
+    Redirected(String),
+    /// Printer requires TLS, the secure endpoint is given
+    TlsRequired(String),
+    /// Server rejected the supplied credentials
+    AuthenticationFailed,
+    /// Connection to the printer could not be established within the connect timeout
+    ConnectTimeout,
+    /// TLS handshake failed, for example the server rejected the client certificate,
+    /// or the client identity could not be loaded
+    TlsError(String),
+}
+
+impl IppError {
+    /// IPP status code corresponding to this error, for forwarding it to another IPP client.
+    ///
+    /// Printer state errors map to `server-error-service-unavailable` when the printer is stopped
+    /// and to `server-error-device-error` otherwise, failed authentication to
+    /// `client-error-not-authenticated` and parameter errors to `client-error-bad-request`.
+    /// Transport and parsing errors have no status code
+    pub fn status_code(&self) -> Option<StatusCode> {
+        match *self {
+            IppError::StatusError(code) => Some(code),
+            IppError::PrinterStopped => Some(StatusCode::ServerErrorServiceUnavailable),
+            IppError::PrinterStateError(_) => Some(StatusCode::ServerErrorDeviceError),
+            IppError::AuthenticationFailed => Some(StatusCode::ClientErrorNotAuthenticated),
+            IppError::ParamError(_) => Some(StatusCode::ClientErrorBadRequest),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for IppError {
@@ -43,7 +87,12 @@ impl fmt::Display for IppError {
             IppError::PrinterStopped => write!(f, "IPP printer stopped"),
             IppError::ParseError(ref e) => write!(f, "{}", e),
             IppError::MissingAttribute => write!(f, "Missing attribute in response"),
-            IppError::InvalidAttributeType => write!(f, "Invalid attribute type"),
+            IppError::InvalidAttributeType(ref e) => write!(f, "Invalid attribute type: {}", e),
+            IppError::Redirected(ref e) => write!(f, "IPP request redirected to {}", e),
+            IppError::TlsRequired(ref e) => write!(f, "IPP printer requires TLS, use {}", e),
+            IppError::AuthenticationFailed => write!(f, "IPP authentication failed"),
+            IppError::ConnectTimeout => write!(f, "IPP printer connection timed out"),
+            IppError::TlsError(ref e) => write!(f, "IPP TLS error: {}", e),
         }
     }
 }
@@ -66,21 +115,51 @@ impl From<reqwest::Error> for IppError {
     }
 }
 
+impl From<TypeMismatch> for IppError {
+    fn from(error: TypeMismatch) -> Self {
+        IppError::InvalidAttributeType(error)
+    }
+}
+
 impl From<ParseError> for IppError {
     fn from(error: ParseError) -> Self {
         IppError::ParseError(error)
     }
 }
 
-impl std::error::Error for IppError {}
+impl std::error::Error for IppError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            IppError::HttpError(ref e) => Some(e),
+            IppError::IOError(ref e) => Some(e),
+            IppError::ParseError(ref e) => Some(e),
+            IppError::InvalidAttributeType(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
 
 /// Builder to create IPP client
+#[cfg_attr(not(feature = "async"), allow(dead_code))]
 pub struct IppClientBuilder {
     uri: String,
     ca_certs: Vec<PathBuf>,
     verify_hostname: bool,
     verify_certificate: bool,
     timeout: u64,
+    connect_timeout: Duration,
+    tcp_nodelay: bool,
+    payload_hold_back: Option<Duration>,
+    follow_redirects: bool,
+    max_inflight_bytes: Option<usize>,
+    auto_tls_upgrade: bool,
+    basic_auth: Option<(String, String)>,
+    unix_socket: Option<PathBuf>,
+    client_identity: Option<(PathBuf, String)>,
+    version: Option<IppVersion>,
+    retain_raw: bool,
+    max_value_size: usize,
+    max_total_size: usize,
 }
 
 impl IppClientBuilder {
@@ -92,6 +171,19 @@ impl IppClientBuilder {
             verify_hostname: true,
             verify_certificate: true,
             timeout: 0,
+            connect_timeout: Duration::from_secs(10),
+            tcp_nodelay: false,
+            payload_hold_back: None,
+            follow_redirects: false,
+            max_inflight_bytes: None,
+            auto_tls_upgrade: false,
+            basic_auth: None,
+            unix_socket: None,
+            client_identity: None,
+            version: None,
+            retain_raw: false,
+            max_value_size: u16::MAX as usize,
+            max_total_size: usize::MAX,
         }
     }
 
@@ -127,27 +219,206 @@ impl IppClientBuilder {
     }
 
     /// Set network timeout in seconds. Default is 0 (no timeout)
+    ///
+    /// The timeout covers the whole request including connecting, sending the document and
+    /// receiving the response. An expired timeout is reported as `IppError::IOError` of kind `TimedOut`.
     pub fn timeout(mut self, timeout: u64) -> Self {
         self.timeout = timeout;
         self
     }
 
+    /// Set timeout for establishing the connection. Default is 10 seconds.
+    ///
+    /// A short connect timeout detects offline printers quickly while `timeout` can stay long
+    /// for printers which are slow to process a job. When it expires the request fails
+    /// with `IppError::ConnectTimeout`.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Enable or disable TCP_NODELAY on the connection socket. Default is false.
+    ///
+    /// Disabling Nagle's algorithm reduces latency of small requests such as frequent status polling.
+    /// Large document uploads are not affected by Nagle's algorithm, so there is little benefit in enabling it
+    /// for print jobs.
+    pub fn tcp_nodelay(mut self, nodelay: bool) -> Self {
+        self.tcp_nodelay = nodelay;
+        self
+    }
+
+    /// Hold the document of a request back for the given time after the IPP header is sent.
+    /// Default is None, the document follows the header immediately.
+    ///
+    /// This is a fixed hold-back rather than full `Expect: 100-continue` support: the request carries
+    /// the `Expect: 100-continue` header, but the underlying HTTP client does not report interim
+    /// 100 responses, so the document is sent when the time expires even if the printer accepted
+    /// the request earlier. A printer which rejects the request with a final response within the time
+    /// saves the upload of the document. The time starts once the connection is established.
+    pub fn payload_hold_back(mut self, timeout: Option<Duration>) -> Self {
+        self.payload_hold_back = timeout;
+        self
+    }
+
+    /// Follow HTTP redirects to another IPP location. Default is false.
+    ///
+    /// When disabled, a redirect response fails with `IppError::Redirected` naming the suggested location.
+    /// Requests which carry a document cannot be replayed and always fail with this error.
+    pub fn follow_redirects(mut self, follow: bool) -> Self {
+        self.follow_redirects = follow;
+        self
+    }
+
+    /// Limit total request body bytes in flight across all clones of the client. Default is no limit.
+    ///
+    /// Uploads wait for capacity before sending further chunks, so concurrent large jobs sharing one client
+    /// use a predictable amount of memory and bandwidth. A single chunk larger than the limit is still sent
+    /// when nothing else is in flight.
+    pub fn max_inflight_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_inflight_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Switch to the secure `ipps` endpoint when the printer requires TLS. Default is false.
+    ///
+    /// The upgrade happens when a plain request is refused with HTTP 426 Upgrade Required, or when
+    /// `capabilities` reports TLS for all printer URIs (uri-security-supported). Requests without a document
+    /// are repeated on the secure endpoint; requests with a document fail with `IppError::TlsRequired`
+    /// and all subsequent requests of this client and its clones use the secure endpoint.
+    pub fn auto_tls_upgrade(mut self, upgrade: bool) -> Self {
+        self.auto_tls_upgrade = upgrade;
+        self
+    }
+
+    /// Use HTTP basic authentication with the given credentials.
+    ///
+    /// Credentials embedded in the URI are used when not set explicitly. When credentials are present
+    /// and the server responds with HTTP 401, the request fails with `IppError::AuthenticationFailed`.
+    pub fn basic_auth(mut self, username: &str, password: &str) -> Self {
+        self.basic_auth = Some((username.to_owned(), password.to_owned()));
+        self
+    }
+
+    /// Present a client certificate during the TLS handshake, for print servers which require
+    /// mutual authentication. The certificate and private key are read from a PKCS#12 archive
+    /// (`.p12` or `.pfx`) protected by `password`, for example created with
+    /// `openssl pkcs12 -export -inkey client.key -in client.crt -out client.p12`.
+    ///
+    /// A missing or invalid archive, as well as a handshake rejected by the server,
+    /// fails the request with `IppError::TlsError`.
+    pub fn client_identity<P>(mut self, path: P, password: &str) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        self.client_identity = Some((path.as_ref().to_owned(), password.to_owned()));
+        self
+    }
+
+    /// IPP version of the operations sent by the client, for example `IppVersion::Ipp20`.
+    /// By default each operation uses its own version, which is 1.1 for most operations.
+    ///
+    /// If the printer responds with `server-error-version-not-supported` and a different version
+    /// in the response header, the request is repeated once with that version. Requests with
+    /// a document payload are not repeated.
+    pub fn version(mut self, version: IppVersion) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Keep a copy of each received response message in memory, available through
+    /// `IppRequestResponse::raw_bytes` and `payload_bytes`. Default is false.
+    ///
+    /// This is meant for debugging, for example to inspect vendor extensions. The copy includes
+    /// any document payload, so memory use grows with the response size. Responses received
+    /// over a Unix socket are never retained.
+    pub fn retain_raw(mut self, retain: bool) -> Self {
+        self.retain_raw = retain;
+        self
+    }
+
+    /// Set maximum length of a single attribute name or value in responses. Default is 65535,
+    /// the protocol maximum. Longer values fail the request with `ParseError::LimitExceeded`
+    pub fn max_value_size(mut self, size: usize) -> Self {
+        self.max_value_size = size;
+        self
+    }
+
+    /// Set maximum size of the header and attributes of responses, the document payload is not included.
+    /// Default is no limit.
+    ///
+    /// The attributes are buffered until they are complete, the limit protects against printers
+    /// which send an endless stream of attributes.
+    pub fn max_total_size(mut self, size: usize) -> Self {
+        self.max_total_size = size;
+        self
+    }
+
+    /// Send requests over the Unix domain socket at the given path instead of TCP,
+    /// for example `/var/run/cups/cups.sock` of the local CUPS server.
+    ///
+    /// The client URI still names the printer, for example `ipp://localhost/printers/foo`, and
+    /// its path is used for the HTTP request. TLS, timeouts and authentication settings do not
+    /// apply to socket connections, CUPS authenticates local clients by their peer credentials.
+    #[cfg(unix)]
+    pub fn unix_socket<P>(mut self, path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        self.unix_socket = Some(path.as_ref().to_owned());
+        self
+    }
+
     /// Build the client
+    #[cfg(feature = "async")]
     pub fn build(self) -> IppClient {
-        IppClient {
-            uri: self.uri,
-            ca_certs: self.ca_certs,
-            verify_hostname: self.verify_hostname,
-            verify_certificate: self.verify_certificate,
-            timeout: self.timeout,
-        }
+        IppClient::from_builder(self)
+    }
+
+    /// Build a blocking client which does not need a tokio runtime.
+    /// Without the default `async` feature only the blocking client is built.
+    ///
+    /// Certificates and the client identity are loaded immediately, so errors in them are reported here
+    /// rather than by the first request.
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> Result<blocking::IppClient, IppError> {
+        blocking::IppClient::from_builder(self)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use ipp_proto::IppValue;
+
     use super::*;
 
+    #[test]
+    fn test_status_code() {
+        assert_eq!(
+            IppError::StatusError(StatusCode::ClientErrorNotFound).status_code(),
+            Some(StatusCode::ClientErrorNotFound)
+        );
+        assert_eq!(
+            IppError::PrinterStopped.status_code(),
+            Some(StatusCode::ServerErrorServiceUnavailable)
+        );
+        assert_eq!(
+            IppError::PrinterStateError(vec!["toner-empty".to_owned()]).status_code(),
+            Some(StatusCode::ServerErrorDeviceError)
+        );
+        assert_eq!(
+            IppError::AuthenticationFailed.status_code(),
+            Some(StatusCode::ClientErrorNotAuthenticated)
+        );
+        assert_eq!(IppError::ConnectTimeout.status_code(), None);
+        assert_eq!(IppError::ParseError(ParseError::Incomplete).status_code(), None);
+    }
+
+    #[test]
+    fn test_invalid_attribute_type() {
+        let error: IppError = IppValue::Keyword("idle".to_owned()).expect_enum().unwrap_err().into();
+        assert_eq!(error.to_string(), "Invalid attribute type: expected Enum, got Keyword");
+    }
+
     #[test]
     fn test_builder() {
         let mut builder = IppClientBuilder::new("foobar");
@@ -157,7 +428,7 @@ mod tests {
         builder = builder.ca_cert(&cert);
         assert_eq!(builder.ca_certs, vec![cert.clone()]);
 
-        builder = builder.ca_certs(&[cert.clone()]);
+        builder = builder.ca_certs([&cert]);
         assert_eq!(builder.ca_certs, vec![cert.clone(), cert.clone()]);
 
         builder = builder.verify_hostname(false);
@@ -169,6 +440,48 @@ mod tests {
         builder = builder.timeout(100);
         assert_eq!(builder.timeout, 100);
 
+        builder = builder.connect_timeout(Duration::from_secs(2));
+        assert_eq!(builder.connect_timeout, Duration::from_secs(2));
+
+        builder = builder.tcp_nodelay(true);
+        assert!(builder.tcp_nodelay);
+
+        builder = builder.payload_hold_back(Some(Duration::from_millis(500)));
+        assert_eq!(builder.payload_hold_back, Some(Duration::from_millis(500)));
+
+        builder = builder.follow_redirects(true);
+        assert!(builder.follow_redirects);
+
+        builder = builder.max_inflight_bytes(65536);
+        assert_eq!(builder.max_inflight_bytes, Some(65536));
+
+        builder = builder.auto_tls_upgrade(true);
+        assert!(builder.auto_tls_upgrade);
+
+        builder = builder.unix_socket("/var/run/cups/cups.sock");
+        assert_eq!(builder.unix_socket, Some(PathBuf::from("/var/run/cups/cups.sock")));
+
+        builder = builder.basic_auth("user", "secret");
+        assert_eq!(builder.basic_auth, Some(("user".to_owned(), "secret".to_owned())));
+
+        builder = builder.client_identity("client.p12", "secret");
+        assert_eq!(
+            builder.client_identity,
+            Some((PathBuf::from("client.p12"), "secret".to_owned()))
+        );
+
+        builder = builder.version(IppVersion::Ipp20);
+        assert_eq!(builder.version, Some(IppVersion::Ipp20));
+
+        builder = builder.retain_raw(true);
+        assert!(builder.retain_raw);
+
+        assert_eq!(builder.max_total_size, usize::MAX);
+        builder = builder.max_value_size(1024).max_total_size(65536);
+        assert_eq!(builder.max_value_size, 1024);
+        assert_eq!(builder.max_total_size, 65536);
+
+        #[cfg(feature = "async")]
         let _ = builder.build();
     }
 }
@@ -0,0 +1,33 @@
+//!
+//! HTTP helpers for the client tests
+//!
+use std::io::BufRead;
+
+// read one HTTP request and return its headers followed by the body. The body is read by
+// Content-Length, a body without it is read up to the last chunk of the chunked encoding
+pub(crate) fn read_request<R: BufRead>(reader: &mut R) -> String {
+    let mut request = String::new();
+    while reader.read_line(&mut request).unwrap() > 0 && !request.ends_with("\r\n\r\n") {}
+    let length = request
+        .lines()
+        .filter_map(|line| {
+            line.to_ascii_lowercase()
+                .strip_prefix("content-length:")
+                .map(str::to_owned)
+        })
+        .find_map(|length| length.trim().parse::<usize>().ok());
+
+    match length {
+        Some(length) => {
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8_lossy(&body));
+        }
+        None => {
+            let mut body = Vec::new();
+            while reader.read_until(b'\n', &mut body).unwrap() > 0 && !body.ends_with(b"\r\n0\r\n\r\n") {}
+            request.push_str(&String::from_utf8_lossy(&body));
+        }
+    }
+    request
+}
@@ -0,0 +1,73 @@
+//!
+//! IPP transport over Unix domain sockets
+//!
+use std::{io, path::PathBuf};
+
+use futures::{future, Future, Stream};
+use hyper::{
+    client::connect::{Connect, Connected, Destination},
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    Body, Chunk, Client, Request,
+};
+use log::debug;
+use tokio::net::UnixStream;
+
+use ipp_proto::{request::IppRequestResponse, AsyncIppParser};
+
+use crate::IppError;
+
+// connects to the socket regardless of the host and port of the request URI
+struct UnixConnector(PathBuf);
+
+impl Connect for UnixConnector {
+    type Transport = UnixStream;
+    type Error = io::Error;
+    type Future = Box<dyn Future<Item = (UnixStream, Connected), Error = io::Error> + Send>;
+
+    fn connect(&self, _dst: Destination) -> Self::Future {
+        Box::new(UnixStream::connect(&self.0).map(|stream| (stream, Connected::new())))
+    }
+}
+
+fn to_io_error(error: hyper::Error) -> io::Error {
+    io::Error::other(error.to_string())
+}
+
+/// Send request to the `url` over the Unix domain socket at `path`, `limits` are the maximum value
+/// and total attribute sizes of the response
+pub(crate) fn send_request(
+    path: PathBuf,
+    url: String,
+    request: IppRequestResponse,
+    limits: (usize, usize),
+) -> impl Future<Item = IppRequestResponse, Error = IppError> + Send {
+    debug!("Connecting to {}", path.display());
+    let client = Client::builder().build::<_, Body>(UnixConnector(path));
+
+    let mut builder = Request::post(url);
+    builder.header(CONTENT_TYPE, "application/ipp");
+    if let Some(length) = request.content_length() {
+        debug!("Setting Content-Length to {}", length);
+        builder.header(CONTENT_LENGTH, length);
+    }
+
+    future::result(builder.body(Body::wrap_stream(request.into_stream())))
+        .map_err(|e| IppError::ParamError(e.to_string()))
+        .and_then(move |request| client.request(request).map_err(|e| IppError::from(to_io_error(e))))
+        .and_then(move |response| {
+            if !response.status().is_success() {
+                return Err(IppError::from(io::Error::other(format!(
+                    "HTTP status {}",
+                    response.status()
+                ))));
+            }
+            let stream: Box<dyn Stream<Item = Chunk, Error = io::Error> + Send> =
+                Box::new(response.into_body().map_err(to_io_error));
+            Ok(AsyncIppParser::from(stream)
+                .max_value_size(limits.0)
+                .max_total_size(limits.1)
+                .map_err(IppError::from))
+        })
+        .flatten()
+        .map(IppRequestResponse::from_parse_result)
+}
@@ -6,28 +6,53 @@ use std::{
     io::{self, Write},
 };
 
-use byteorder::{BigEndian, WriteBytesExt};
+use byteorder::WriteBytesExt;
+use num_traits::FromPrimitive;
 
-use crate::{ipp::*, IppValue, IppWriter};
+use crate::{
+    ipp::*,
+    model::{JobAttributes, PrinterAttributes},
+    value::write_len,
+    IppValue, IppWriter,
+};
 
 pub const ATTRIBUTES_CHARSET: &str = "attributes-charset";
 pub const ATTRIBUTES_NATURAL_LANGUAGE: &str = "attributes-natural-language";
 pub const CHARSET_CONFIGURED: &str = "charset-configured";
 pub const CHARSET_SUPPORTED: &str = "charset-supported";
+pub const COMPRESSION: &str = "compression";
 pub const COMPRESSION_SUPPORTED: &str = "compression-supported";
+pub const DOCUMENT_CHARSET: &str = "document-charset";
+pub const DOCUMENT_NATURAL_LANGUAGE: &str = "document-natural-language";
+pub const DOCUMENT_NAME: &str = "document-name";
+pub const DOCUMENT_NUMBER: &str = "document-number";
+pub const DOCUMENT_FORMAT: &str = "document-format";
 pub const DOCUMENT_FORMAT_DEFAULT: &str = "document-format-default";
 pub const DOCUMENT_FORMAT_SUPPORTED: &str = "document-format-supported";
+pub const DOCUMENT_STATE: &str = "document-state";
 pub const GENERATED_NATURAL_LANGUAGE_SUPPORTED: &str = "generated-natural-language-supported";
 pub const IPP_VERSIONS_SUPPORTED: &str = "ipp-versions-supported";
+pub const IPP_FEATURES_SUPPORTED: &str = "ipp-features-supported";
 pub const NATURAL_LANGUAGE_CONFIGURED: &str = "natural-language-configured";
 pub const OPERATIONS_SUPPORTED: &str = "operations-supported";
 pub const PDL_OVERRIDE_SUPPORTED: &str = "pdl-override-supported";
 pub const PRINTER_IS_ACCEPTING_JOBS: &str = "printer-is-accepting-jobs";
 pub const PRINTER_MAKE_AND_MODEL: &str = "printer-make-and-model";
+pub const PRINTER_ALERT: &str = "printer-alert";
+pub const PRINTER_ALERT_DESCRIPTION: &str = "printer-alert-description";
+pub const PRINTER_CONFIG_CHANGE_TIME: &str = "printer-config-change-time";
+pub const PRINTER_DEVICE_ID: &str = "printer-device-id";
+pub const PRINTER_FIRMWARE_NAME: &str = "printer-firmware-name";
+pub const PRINTER_FIRMWARE_PATCHES: &str = "printer-firmware-patches";
+pub const PRINTER_FIRMWARE_STRING_VERSION: &str = "printer-firmware-string-version";
 pub const PRINTER_NAME: &str = "printer-name";
 pub const PRINTER_STATE: &str = "printer-state";
+pub const PRINTER_STATE_CHANGE_TIME: &str = "printer-state-change-time";
 pub const PRINTER_STATE_MESSAGE: &str = "printer-state-message";
 pub const PRINTER_STATE_REASONS: &str = "printer-state-reasons";
+pub const PRINTER_SUPPLY: &str = "printer-supply";
+pub const PRINTER_SUPPLY_DESCRIPTION: &str = "printer-supply-description";
+pub const PRINTER_SUPPLY_INFO_URI: &str = "printer-supply-info-uri";
 pub const PRINTER_UP_TIME: &str = "printer-up-time";
 pub const PRINTER_URI: &str = "printer-uri";
 pub const PRINTER_URI_SUPPORTED: &str = "printer-uri-supported";
@@ -35,25 +60,41 @@ pub const QUEUED_JOB_COUNT: &str = "queued-job-count";
 pub const URI_AUTHENTICATION_SUPPORTED: &str = "uri-authentication-supported";
 pub const URI_SECURITY_SUPPORTED: &str = "uri-security-supported";
 pub const JOB_ID: &str = "job-id";
+pub const JOB_HOLD_UNTIL: &str = "job-hold-until";
+pub const JOB_CREATION_ATTRIBUTES_SUPPORTED: &str = "job-creation-attributes-supported";
+pub const JOB_MANDATORY_ATTRIBUTES: &str = "job-mandatory-attributes";
 pub const JOB_NAME: &str = "job-name";
 pub const JOB_STATE: &str = "job-state";
 pub const JOB_STATE_REASONS: &str = "job-state-reasons";
 pub const JOB_URI: &str = "job-uri";
 pub const LAST_DOCUMENT: &str = "last-document";
+pub const DOCUMENT_URI: &str = "document-uri";
 pub const REQUESTING_USER_NAME: &str = "requesting-user-name";
 pub const STATUS_MESSAGE: &str = "status-message";
 pub const REQUESTED_ATTRIBUTES: &str = "requested-attributes";
 pub const SIDES_SUPPORTED: &str = "sides-supported";
+pub const NOTIFY_EVENTS: &str = "notify-events";
+pub const NOTIFY_GET_INTERVAL: &str = "notify-get-interval";
+pub const NOTIFY_LEASE_DURATION: &str = "notify-lease-duration";
+pub const NOTIFY_PULL_METHOD: &str = "notify-pull-method";
+pub const NOTIFY_PULL_METHOD_SUPPORTED: &str = "notify-pull-method-supported";
+pub const NOTIFY_SEQUENCE_NUMBER: &str = "notify-sequence-number";
+pub const NOTIFY_SEQUENCE_NUMBERS: &str = "notify-sequence-numbers";
+pub const NOTIFY_SUBSCRIPTION_ID: &str = "notify-subscription-id";
+pub const NOTIFY_SUBSCRIPTION_IDS: &str = "notify-subscription-ids";
 pub const OUTPUT_MODE_SUPPORTED: &str = "output-mode-supported";
 pub const COLOR_SUPPORTED: &str = "color-supported";
 pub const PRINTER_INFO: &str = "printer-info";
+pub const PRINTER_KIND: &str = "printer-kind";
 pub const PRINTER_LOCATION: &str = "printer-location";
+pub const PRINTER_GEO_LOCATION: &str = "printer-geo-location";
 pub const PRINTER_MORE_INFO: &str = "printer-more-info";
 pub const PRINTER_RESOLUTION_DEFAULT: &str = "printer-resolution-default";
 pub const PRINTER_RESOLUTION_SUPPORTED: &str = "printer-resolution-supported";
 pub const COPIES_SUPPORTED: &str = "copies-supported";
 pub const COPIES_DEFAULT: &str = "copies-default";
 pub const SIDES_DEFAULT: &str = "sides-default";
+pub const PRINT_QUALITY: &str = "print-quality";
 pub const PRINT_QUALITY_DEFAULT: &str = "print-quality-default";
 pub const PRINT_QUALITY_SUPPORTED: &str = "print-quality-supported";
 pub const FINISHINGS_DEFAULT: &str = "finishings-default";
@@ -62,20 +103,49 @@ pub const OUTPUT_BIN_DEFAULT: &str = "output-bin-default";
 pub const OUTPUT_BIN_SUPPORTED: &str = "output-bin-supported";
 pub const ORIENTATION_REQUESTED_DEFAULT: &str = "orientation-requested-default";
 pub const ORIENTATION_REQUESTED_SUPPORTED: &str = "orientation-requested-supported";
+pub const MEDIA: &str = "media";
+pub const COPIES: &str = "copies";
+pub const SIDES: &str = "sides";
 pub const MEDIA_DEFAULT: &str = "media-default";
 pub const MEDIA_SUPPORTED: &str = "media-supported";
+pub const MEDIA_READY: &str = "media-ready";
+pub const MEDIA_COL: &str = "media-col";
+pub const MEDIA_COL_READY: &str = "media-col-ready";
+pub const MEDIA_SIZE: &str = "media-size";
+pub const MEDIA_SIZE_NAME: &str = "media-size-name";
+pub const MEDIA_TYPE: &str = "media-type";
+pub const MEDIA_SOURCE: &str = "media-source";
+pub const MEDIA_BOTTOM_MARGIN: &str = "media-bottom-margin";
+pub const MEDIA_LEFT_MARGIN: &str = "media-left-margin";
+pub const MEDIA_RIGHT_MARGIN: &str = "media-right-margin";
+pub const MEDIA_TOP_MARGIN: &str = "media-top-margin";
+pub const X_DIMENSION: &str = "x-dimension";
+pub const Y_DIMENSION: &str = "y-dimension";
 pub const PAGES_PER_MINUTE: &str = "pages-per-minute";
 pub const COLOR_MODE_SUPPORTED: &str = "color-mode-supported";
 pub const PRINT_COLOR_MODE_SUPPORTED: &str = "print-color-mode-supported";
+pub const MULTIPLE_DOCUMENT_HANDLING: &str = "multiple-document-handling";
+pub const PRINT_CONTENT_OPTIMIZE: &str = "print-content-optimize";
+pub const PRINT_CONTENT_OPTIMIZE_SUPPORTED: &str = "print-content-optimize-supported";
+pub const PROOF_PRINT: &str = "proof-print";
+pub const PROOF_PRINT_COPIES: &str = "proof-print-copies";
+pub const WHICH_JOBS: &str = "which-jobs";
+pub const MY_JOBS: &str = "my-jobs";
+pub const LIMIT: &str = "limit";
+pub const SEPARATOR_SHEETS: &str = "separator-sheets";
+pub const SEPARATOR_SHEETS_TYPE: &str = "separator-sheets-type";
+pub const IDENTIFY_ACTIONS: &str = "identify-actions";
+pub const MESSAGE: &str = "message";
 
-const HEADER_ATTRS: [&str; 3] = [ATTRIBUTES_CHARSET, ATTRIBUTES_NATURAL_LANGUAGE, PRINTER_URI];
+// attributes which must start the operation group, the target is printer-uri or job-uri
+const HEADER_ATTRS: [&str; 4] = [ATTRIBUTES_CHARSET, ATTRIBUTES_NATURAL_LANGUAGE, PRINTER_URI, JOB_URI];
 
 fn is_header_attr(attr: &str) -> bool {
-    HEADER_ATTRS.iter().any(|&at| at == attr)
+    HEADER_ATTRS.contains(&attr)
 }
 
 /// `IppAttribute` represents an IPP attribute
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct IppAttribute {
     /// Attribute name
     name: String,
@@ -104,6 +174,17 @@ impl IppAttribute {
     pub fn value(&self) -> &IppValue {
         &self.value
     }
+
+    /// Return mutable attribute value. The name cannot be changed, so the attribute
+    /// stays reachable under its key in the group
+    pub fn value_mut(&mut self) -> &mut IppValue {
+        &mut self.value
+    }
+
+    /// Replace attribute value
+    pub fn set_value(&mut self, value: IppValue) {
+        self.value = value;
+    }
 }
 
 impl IppWriter for IppAttribute {
@@ -114,7 +195,7 @@ impl IppWriter for IppAttribute {
         writer.write_u8(self.value.to_tag() as u8)?;
         retval += 1;
 
-        writer.write_u16::<BigEndian>(self.name.len() as u16)?;
+        write_len(writer, self.name.len())?;
         retval += 2;
 
         writer.write_all(self.name.as_bytes())?;
@@ -127,7 +208,7 @@ impl IppWriter for IppAttribute {
 }
 
 /// Attribute group
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct IppAttributeGroup {
     tag: DelimiterTag,
     attributes: HashMap<String, IppAttribute>,
@@ -162,12 +243,23 @@ impl IppAttributeGroup {
 #[derive(Clone, Debug, Default)]
 pub struct IppAttributes {
     groups: Vec<IppAttributeGroup>,
+    explicit_order: bool,
 }
 
+// lists are equal when they hold the same groups, a parsed list does not know whether
+// the groups were created with `begin_group`
+impl PartialEq for IppAttributes {
+    fn eq(&self, other: &IppAttributes) -> bool {
+        self.groups == other.groups
+    }
+}
+
+impl Eq for IppAttributes {}
+
 impl IppAttributes {
     /// Create attribute list
     pub fn new() -> IppAttributes {
-        IppAttributes { ..Default::default()}
+        IppAttributes { ..Default::default() }
     }
 
     /// Get all groups
@@ -180,6 +272,11 @@ impl IppAttributes {
         &mut self.groups
     }
 
+    /// Iterate over all groups together with their delimiter tags, in the order they were received or created
+    pub fn iter_groups(&self) -> impl Iterator<Item = (DelimiterTag, &IppAttributeGroup)> {
+        self.groups.iter().map(|g| (g.tag(), g))
+    }
+
     /// Get a list of attribute groups matching a given delimiter tag
     pub fn groups_of(&self, tag: DelimiterTag) -> Vec<&IppAttributeGroup> {
         self.groups.iter().filter(|g| g.tag == tag).collect()
@@ -198,42 +295,164 @@ impl IppAttributes {
             self.groups_mut().push(new_group);
         }
     }
+
+    // attribute from the first printer attributes group
+    fn printer_attribute(&self, name: &str) -> Option<&IppAttribute> {
+        self.groups_of(DelimiterTag::PrinterAttributes)
+            .first()
+            .and_then(|g| g.attributes().get(name))
+    }
+
+    /// First printer attributes group with typed getters
+    pub fn printer(&self) -> Option<PrinterAttributes<'_>> {
+        self.groups_of(DelimiterTag::PrinterAttributes)
+            .first()
+            .map(|g| PrinterAttributes::new(g))
+    }
+
+    /// Job attributes groups with typed getters, for example the jobs of a Get-Jobs response
+    pub fn jobs(&self) -> Vec<JobAttributes<'_>> {
+        self.groups_of(DelimiterTag::JobAttributes)
+            .into_iter()
+            .map(JobAttributes::new)
+            .collect()
+    }
+
+    /// Printer state (printer-state) from the printer attributes group.
+    /// Returns None if the attribute is absent or is not a known enum value
+    pub fn printer_state(&self) -> Option<PrinterState> {
+        self.printer_attribute(PRINTER_STATE)
+            .and_then(|attr| attr.value().as_enum())
+            .and_then(|v| PrinterState::from_i32(*v))
+    }
+
+    /// Job state (job-state) from the first job attributes group, for example in a Get-Job-Attributes response.
+    /// Returns None if the attribute is absent or is not a known enum value
+    pub fn job_state(&self) -> Option<JobState> {
+        self.groups_of(DelimiterTag::JobAttributes)
+            .first()
+            .and_then(|g| g.attributes().get(JOB_STATE))
+            .and_then(|attr| attr.value().as_enum())
+            .and_then(|v| JobState::from_i32(*v))
+    }
+
+    /// Printer state reason keywords (printer-state-reasons) from the printer attributes group
+    pub fn printer_state_reasons(&self) -> Vec<String> {
+        self.printer_attribute(PRINTER_STATE_REASONS)
+            .map(|attr| {
+                attr.value()
+                    .into_iter()
+                    .filter_map(|v| v.as_keyword())
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether the printer accepts new jobs (printer-is-accepting-jobs) from the printer attributes group
+    pub fn is_accepting_jobs(&self) -> Option<bool> {
+        self.printer_attribute(PRINTER_IS_ACCEPTING_JOBS)
+            .and_then(|attr| attr.value().as_boolean())
+            .cloned()
+    }
+
+    /// Number of jobs queued on the printer (queued-job-count) from the printer attributes group
+    pub fn queued_job_count(&self) -> Option<i32> {
+        self.printer_attribute(QUEUED_JOB_COUNT)
+            .and_then(|attr| attr.value().as_integer())
+            .cloned()
+    }
+
+    /// Supported document formats (document-format-supported) from the printer attributes group
+    pub fn document_format_supported(&self) -> Vec<String> {
+        self.printer_attribute(DOCUMENT_FORMAT_SUPPORTED)
+            .map(|attr| {
+                attr.value()
+                    .into_iter()
+                    .filter_map(|v| v.as_mimemediatype())
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Start a new attribute group, even if a group with this tag already exists.
+    ///
+    /// Once called, all groups are serialized exactly in the order they were created. By default the
+    /// first operation group is moved to the front and the other groups keep their order.
+    pub fn begin_group(&mut self, tag: DelimiterTag) {
+        self.explicit_order = true;
+        self.groups.push(IppAttributeGroup::new(tag));
+    }
+
+    /// Add attribute to the most recently created group
+    pub fn add_to_last_group(&mut self, attribute: IppAttribute) {
+        match self.groups.last_mut() {
+            Some(group) => {
+                group.attributes_mut().insert(attribute.name().to_owned(), attribute);
+            }
+            None => self.add(DelimiterTag::OperationAttributes, attribute),
+        }
+    }
+}
+
+// serialize group attributes, header attributes of the operation group go first
+fn write_group(group: &IppAttributeGroup, writer: &mut dyn Write) -> io::Result<usize> {
+    let mut retval = 0;
+
+    if group.tag() == DelimiterTag::OperationAttributes {
+        for hdr in &HEADER_ATTRS {
+            if let Some(attr) = group.attributes().get(*hdr) {
+                retval += attr.write(writer)?
+            }
+        }
+    }
+
+    for (_, attr) in group
+        .attributes()
+        .iter()
+        .filter(|&(_, v)| group.tag() != DelimiterTag::OperationAttributes || !is_header_attr(v.name()))
+    {
+        retval += attr.write(writer)?;
+    }
+    Ok(retval)
 }
 
 impl IppWriter for IppAttributes {
     /// Serialize attribute list into binary stream
     fn write(&self, writer: &mut dyn Write) -> io::Result<usize> {
-        // first send the header attributes
-        writer.write_u8(DelimiterTag::OperationAttributes as u8)?;
+        let mut retval = 0;
 
-        let mut retval = 1;
+        if self.explicit_order {
+            for group in self
+                .groups
+                .iter()
+                .filter(|g| g.tag() != DelimiterTag::UnknownAttributes)
+            {
+                writer.write_u8(group.tag() as u8)?;
+                retval += 1 + write_group(group, writer)?;
+            }
+        } else {
+            // operation group always goes first, even if empty, the other groups follow in the order they were added
+            writer.write_u8(DelimiterTag::OperationAttributes as u8)?;
+            retval += 1;
 
-        if let Some(group) = self.groups_of(DelimiterTag::OperationAttributes).get(0) {
-            for hdr in &HEADER_ATTRS {
-                if let Some(attr) = group.attributes().get(*hdr) {
-                    retval += attr.write(writer)?
-                }
+            let operation = self
+                .groups
+                .iter()
+                .position(|g| g.tag() == DelimiterTag::OperationAttributes);
+            if let Some(index) = operation {
+                retval += write_group(&self.groups[index], writer)?;
             }
-        }
 
-        // now the rest
-        for hdr in &[
-            DelimiterTag::OperationAttributes,
-            DelimiterTag::JobAttributes,
-            DelimiterTag::PrinterAttributes,
-        ] {
-            if let Some(group) = self.groups_of(*hdr).get(0) {
-                if group.tag() != DelimiterTag::OperationAttributes {
-                    writer.write_u8(group.tag() as u8)?;
-                    retval += 1;
-                }
-                for (_, attr) in group
-                    .attributes()
-                    .iter()
-                    .filter(|&(_, v)| group.tag() != DelimiterTag::OperationAttributes || !is_header_attr(v.name()))
-                {
-                    retval += attr.write(writer)?;
-                }
+            for (_, group) in self
+                .groups
+                .iter()
+                .enumerate()
+                .filter(|&(i, g)| Some(i) != operation && g.tag() != DelimiterTag::UnknownAttributes)
+            {
+                writer.write_u8(group.tag() as u8)?;
+                retval += 1 + write_group(group, writer)?;
             }
         }
         writer.write_u8(DelimiterTag::EndOfAttributes as u8)?;
@@ -242,3 +461,264 @@ impl IppWriter for IppAttributes {
         Ok(retval)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::request::IppRequestResponse;
+
+    use super::*;
+
+    #[test]
+    fn test_set_value() {
+        let mut request = IppRequestResponse::new(
+            IppVersion::Ipp11,
+            Operation::GetJobs,
+            Some("ipp://internal/printers/foo"),
+        );
+        request.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage("alice".to_owned())),
+        );
+
+        let group = &mut request.attributes_mut().groups_mut()[0];
+        let attr = group.attributes_mut().get_mut(PRINTER_URI).unwrap();
+        attr.set_value(IppValue::Uri("ipp://public/printers/foo".to_owned()));
+        assert_eq!(attr.name(), PRINTER_URI);
+        if let IppValue::NameWithoutLanguage(ref mut name) = *group
+            .attributes_mut()
+            .get_mut(REQUESTING_USER_NAME)
+            .unwrap()
+            .value_mut()
+        {
+            name.push_str("@example.com");
+        }
+
+        let mut buf = Vec::new();
+        request.write(&mut buf).unwrap();
+        let parsed = IppRequestResponse::from_bytes(&buf).unwrap();
+        let op = &parsed.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(PRINTER_URI).map(|a| a.value()),
+            Some(&IppValue::Uri("ipp://public/printers/foo".to_owned()))
+        );
+        assert_eq!(
+            op.attributes().get(REQUESTING_USER_NAME).map(|a| a.value()),
+            Some(&IppValue::NameWithoutLanguage("alice@example.com".to_owned()))
+        );
+    }
+
+    // names of the operation attributes in wire order
+    fn operation_attribute_names(data: &[u8]) -> Vec<String> {
+        assert_eq!(data[8], DelimiterTag::OperationAttributes as u8);
+        let mut names = Vec::new();
+        let mut pos = 9;
+        while data[pos] >= 0x10 {
+            let len = |at: usize| (usize::from(data[at]) << 8) | usize::from(data[at + 1]);
+            let name_len = len(pos + 1);
+            names.push(String::from_utf8(data[pos + 3..pos + 3 + name_len].to_vec()).unwrap());
+            pos += 3 + name_len;
+            pos += 2 + len(pos);
+        }
+        names
+    }
+
+    #[test]
+    fn test_header_attribute_order() {
+        let mut request = IppRequestResponse::new(IppVersion::Ipp11, Operation::GetJobs, Some("ipp://localhost"));
+        for name in &["a", "b", "c", "d", "e", "f"] {
+            request.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(name, IppValue::Integer(1)),
+            );
+        }
+        let names = operation_attribute_names(&request.to_bytes().unwrap());
+        assert_eq!(
+            names[..3],
+            [ATTRIBUTES_CHARSET, ATTRIBUTES_NATURAL_LANGUAGE, PRINTER_URI]
+        );
+        assert_eq!(names.len(), 9);
+
+        let mut request = IppRequestResponse::new(IppVersion::Ipp11, Operation::CancelJob, None);
+        for name in &["a", "b", "c", "d", "e", "f"] {
+            request.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(name, IppValue::Integer(1)),
+            );
+        }
+        request.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_URI, IppValue::Uri("ipp://localhost/jobs/1".to_owned())),
+        );
+        let names = operation_attribute_names(&request.to_bytes().unwrap());
+        assert_eq!(names[..3], [ATTRIBUTES_CHARSET, ATTRIBUTES_NATURAL_LANGUAGE, JOB_URI]);
+        assert_eq!(names.len(), 9);
+    }
+
+    #[test]
+    fn test_write_all_groups() {
+        let mut response = IppRequestResponse::new_response(IppVersion::Ipp11, StatusCode::SuccessfulOK, 1);
+        let attrs = response.attributes_mut();
+        attrs.add(
+            DelimiterTag::JobAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(1)),
+        );
+        // operation group added after the job group
+        let operation = attrs.groups_mut().remove(0);
+        attrs.groups_mut().push(operation);
+        attrs.add(
+            DelimiterTag::DocumentAttributes,
+            IppAttribute::new(DOCUMENT_NUMBER, IppValue::Integer(1)),
+        );
+        let mut job = IppAttributeGroup::new(DelimiterTag::JobAttributes);
+        job.attributes_mut()
+            .insert(JOB_ID.to_owned(), IppAttribute::new(JOB_ID, IppValue::Integer(2)));
+        attrs.groups_mut().push(job);
+
+        let parsed = IppRequestResponse::from_bytes(&response.to_bytes().unwrap()).unwrap();
+        let groups = parsed
+            .attributes()
+            .iter_groups()
+            .map(|(tag, g)| (tag, g.attributes().get(JOB_ID).map(|a| a.value().clone())))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            vec![
+                (DelimiterTag::OperationAttributes, None),
+                (DelimiterTag::JobAttributes, Some(IppValue::Integer(1))),
+                (DelimiterTag::DocumentAttributes, None),
+                (DelimiterTag::JobAttributes, Some(IppValue::Integer(2))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_job_state() {
+        let mut attrs = IppAttributes::new();
+        assert_eq!(attrs.job_state(), None);
+        attrs.add(
+            DelimiterTag::JobAttributes,
+            IppAttribute::new(JOB_STATE, IppValue::Enum(JobState::ProcessingStopped as i32)),
+        );
+        assert_eq!(attrs.job_state(), Some(JobState::ProcessingStopped));
+    }
+
+    #[test]
+    fn test_printer_accessors() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE, IppValue::Enum(PrinterState::Processing as i32)),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_STATE_REASONS,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("media-low".to_owned()),
+                    IppValue::Keyword("toner-low".to_owned()),
+                ]),
+            ),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                DOCUMENT_FORMAT_SUPPORTED,
+                IppValue::MimeMediaType("application/pdf".to_owned()),
+            ),
+        );
+
+        assert_eq!(attrs.printer_state(), Some(PrinterState::Processing));
+        assert_eq!(attrs.printer_state_reasons(), vec!["media-low", "toner-low"]);
+        assert_eq!(attrs.document_format_supported(), vec!["application/pdf"]);
+
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE, IppValue::Keyword("idle".to_owned())),
+        );
+        assert_eq!(attrs.printer_state(), None);
+        assert!(attrs.printer_state_reasons().is_empty());
+        assert!(attrs.document_format_supported().is_empty());
+    }
+
+    #[test]
+    fn test_accepting_jobs_and_queue() {
+        let mut attrs = IppAttributes::new();
+        assert_eq!(attrs.is_accepting_jobs(), None);
+        assert_eq!(attrs.queued_job_count(), None);
+
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_IS_ACCEPTING_JOBS, IppValue::Boolean(false)),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(QUEUED_JOB_COUNT, IppValue::Integer(4)),
+        );
+        assert_eq!(attrs.is_accepting_jobs(), Some(false));
+        assert_eq!(attrs.queued_job_count(), Some(4));
+
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::JobAttributes,
+            IppAttribute::new(QUEUED_JOB_COUNT, IppValue::Integer(4)),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_IS_ACCEPTING_JOBS, IppValue::Keyword("true".to_owned())),
+        );
+        assert_eq!(attrs.is_accepting_jobs(), None);
+        assert_eq!(attrs.queued_job_count(), None);
+    }
+
+    #[test]
+    fn test_iter_groups() {
+        let data = vec![
+            1, 1, 0, 0, 0, 0, 0, 0, 1, 4, 0x21, 0, 1, b'a', 0, 4, 0, 0, 0, 1, 2, 0x21, 0, 1, b'b', 0, 4, 0, 0, 0, 2, 4,
+            0x21, 0, 1, b'c', 0, 4, 0, 0, 0, 3, 3,
+        ];
+        let result = crate::parser::IppParser::new(&mut io::Cursor::new(data))
+            .parse()
+            .unwrap();
+
+        let dump: Vec<String> = result
+            .attributes
+            .iter_groups()
+            .map(|(tag, group)| {
+                let names: Vec<&str> = group.attributes().keys().map(String::as_str).collect();
+                format!("{:?}: {}", tag, names.join(","))
+            })
+            .collect();
+        assert_eq!(
+            dump,
+            vec![
+                "OperationAttributes: ",
+                "PrinterAttributes: a",
+                "JobAttributes: b",
+                "PrinterAttributes: c"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_attributes_eq() {
+        let copies = IppAttribute::new("copies", IppValue::Integer(2));
+        let sides = IppAttribute::new("sides", IppValue::Keyword("one-sided".to_owned()));
+
+        let mut first = IppAttributes::new();
+        first.add(DelimiterTag::JobAttributes, copies.clone());
+        first.add(DelimiterTag::JobAttributes, sides.clone());
+
+        let mut second = IppAttributes::new();
+        second.add(DelimiterTag::JobAttributes, sides);
+        second.add(DelimiterTag::JobAttributes, copies.clone());
+        assert_eq!(first, second);
+
+        second.add(
+            DelimiterTag::JobAttributes,
+            IppAttribute::new("copies", IppValue::Integer(3)),
+        );
+        assert_ne!(first, second);
+        assert_ne!(copies, IppAttribute::new("copies", IppValue::Enum(2)));
+    }
+}
@@ -1,16 +1,232 @@
+use log::warn;
+use num_traits::FromPrimitive;
+
 use crate::{
-    attribute::IppAttribute,
-    operation::{CreateJob, GetPrinterAttributes, IppOperation, PrintJob, SendDocument},
-    IppJobSource,
+    attribute::{
+        IppAttribute, COMPRESSION, DOCUMENT_CHARSET, DOCUMENT_FORMAT, DOCUMENT_NATURAL_LANGUAGE,
+        JOB_MANDATORY_ATTRIBUTES, MEDIA, MEDIA_COL, MULTIPLE_DOCUMENT_HANDLING, PRINT_CONTENT_OPTIMIZE, PRINT_QUALITY,
+        PROOF_PRINT, PROOF_PRINT_COPIES, SEPARATOR_SHEETS,
+    },
+    capabilities::PrinterCapabilities,
+    ipp::PrintQuality,
+    model::{Compression, ContentOptimize, MediaCol, MultipleDocumentHandling, SeparatorSheets},
+    operation::{
+        CancelJob, CancelSubscription, CreateJob, CreatePrinterSubscriptions, GetDocuments, GetJobAttributes, GetJobs,
+        GetNotifications, GetPrinterAttributes, HoldJob, IdentifyPrinter, IppOperation, PausePrinter, PrintJob,
+        ReleaseJob, RenewSubscription, ResumePrinter, SendDocument, SendUri, ValidateJob,
+    },
+    IppJobSource, IppValue,
 };
 
+// job and operation attributes collected by the builders of job creation operations
+#[derive(Default)]
+struct JobTemplate {
+    operation_attributes: Vec<IppAttribute>,
+    attributes: Vec<IppAttribute>,
+    creation_attributes: Option<Vec<String>>,
+    quality_supported: Option<Vec<PrintQuality>>,
+    content_optimize_supported: Option<Vec<ContentOptimize>>,
+}
+
+impl JobTemplate {
+    fn check_capabilities(&mut self, capabilities: &PrinterCapabilities) {
+        let supported = capabilities.job_creation_attributes();
+        self.creation_attributes = if supported.is_empty() { None } else { Some(supported) };
+        let quality = capabilities.print_quality_supported();
+        self.quality_supported = if quality.is_empty() { None } else { Some(quality) };
+        let optimize = capabilities.print_content_optimize_supported();
+        self.content_optimize_supported = if optimize.is_empty() { None } else { Some(optimize) };
+    }
+
+    fn is_supported(&self, attribute: &IppAttribute) -> bool {
+        if let Some(ref supported) = self.creation_attributes {
+            if !supported.iter().any(|s| s == attribute.name()) {
+                return false;
+            }
+        }
+        match attribute.name() {
+            PRINT_QUALITY => self.quality_supported.as_ref().is_none_or(|supported| {
+                attribute
+                    .value()
+                    .as_enum()
+                    .and_then(|v| PrintQuality::from_i32(*v))
+                    .is_some_and(|quality| supported.contains(&quality))
+            }),
+            PRINT_CONTENT_OPTIMIZE => self.content_optimize_supported.as_ref().is_none_or(|supported| {
+                attribute
+                    .value()
+                    .as_keyword()
+                    .and_then(|v| ContentOptimize::from_keyword(v))
+                    .is_some_and(|optimize| supported.contains(&optimize))
+            }),
+            _ => true,
+        }
+    }
+
+    // names of job attributes missing from job-creation-attributes-supported or with unsupported values
+    fn unsupported_attributes(&self) -> Vec<&str> {
+        self.attributes
+            .iter()
+            .filter(|attr| !self.is_supported(attr))
+            .map(IppAttribute::name)
+            .collect()
+    }
+
+    // warn about unsupported attributes and split into operation and job attributes
+    fn into_attributes(self) -> (Vec<IppAttribute>, Vec<IppAttribute>) {
+        for name in self.unsupported_attributes() {
+            warn!("Job attribute {} is not supported by the printer", name);
+        }
+        (self.operation_attributes, self.attributes)
+    }
+}
+
+// setters of job template attributes for builders with a `template: JobTemplate` field
+macro_rules! job_template_methods {
+    () => {
+        /// Specify custom job attribute
+        pub fn attribute(mut self, attribute: IppAttribute) -> Self {
+            self.template.attributes.push(attribute);
+            self
+        }
+
+        /// Specify custom job attribute, for example `job-priority`. Same as `attribute`
+        pub fn job_attribute(self, attribute: IppAttribute) -> Self {
+            self.attribute(attribute)
+        }
+
+        /// Specify custom operation attribute, for vendor or less common attributes without a dedicated method
+        pub fn operation_attribute(mut self, attribute: IppAttribute) -> Self {
+            self.template.operation_attributes.push(attribute);
+            self
+        }
+
+        /// Check job attributes against job-creation-attributes-supported of the printer, print-quality
+        /// against print-quality-supported and print-content-optimize against print-content-optimize-supported.
+        ///
+        /// Attributes which the printer does not accept at job creation or with unsupported values are logged
+        /// as warnings on build, use `unsupported_attributes` to reject them instead. Nothing is checked against
+        /// a list which the printer does not report.
+        pub fn check_capabilities(mut self, capabilities: &PrinterCapabilities) -> Self {
+            self.template.check_capabilities(capabilities);
+            self
+        }
+
+        /// Names of the job attributes which are not accepted according to `check_capabilities`
+        pub fn unsupported_attributes(&self) -> Vec<&str> {
+            self.template.unsupported_attributes()
+        }
+
+        /// Specify media-col job attribute
+        pub fn media_col(self, media_col: &MediaCol) -> Self {
+            self.attribute(IppAttribute::new(MEDIA_COL, media_col.to_value()))
+        }
+
+        /// Specify print-quality job attribute
+        pub fn print_quality(self, quality: PrintQuality) -> Self {
+            self.attribute(IppAttribute::new(PRINT_QUALITY, IppValue::Enum(quality as i32)))
+        }
+
+        /// Specify print-content-optimize job attribute
+        pub fn content_optimize(self, optimize: ContentOptimize) -> Self {
+            self.attribute(IppAttribute::new(
+                PRINT_CONTENT_OPTIMIZE,
+                IppValue::Keyword(optimize.as_keyword().to_owned()),
+            ))
+        }
+
+        /// Specify multiple-document-handling job attribute, which controls collation of copies
+        pub fn multiple_document_handling(self, handling: MultipleDocumentHandling) -> Self {
+            self.attribute(IppAttribute::new(
+                MULTIPLE_DOCUMENT_HANDLING,
+                IppValue::Keyword(handling.as_keyword().to_owned()),
+            ))
+        }
+
+        /// Specify proof-print job attribute: print `copies` proof copies on the job media first.
+        ///
+        /// The printer holds the job after printing the proof, the full run (`copies` job attribute)
+        /// is printed once the job is released. Held proof jobs are listed by `get_jobs().which_jobs("proof-print")`
+        pub fn proof_print(self, copies: i32) -> Self {
+            self.attribute(IppAttribute::new(
+                PROOF_PRINT,
+                IppValue::collection(vec![(PROOF_PRINT_COPIES, IppValue::Integer(copies))]),
+            ))
+        }
+
+        /// Specify proof-print job attribute with the media to print proof copies on
+        pub fn proof_print_on_media(self, copies: i32, media: &str) -> Self {
+            self.attribute(IppAttribute::new(
+                PROOF_PRINT,
+                IppValue::collection(vec![
+                    (MEDIA, IppValue::Keyword(media.to_owned())),
+                    (PROOF_PRINT_COPIES, IppValue::Integer(copies)),
+                ]),
+            ))
+        }
+
+        /// Specify separator-sheets job attribute.
+        /// Use `SeparatorSheets::is_supported_by` to check the media against printer capabilities
+        pub fn separator_sheets(self, separator_sheets: &SeparatorSheets) -> Self {
+            self.attribute(IppAttribute::new(SEPARATOR_SHEETS, separator_sheets.to_value()))
+        }
+
+        /// Specify job-mandatory-attributes: job template attributes which the printer must not ignore,
+        /// for example `&["sides", "media"]`
+        pub fn mandatory_attributes<T>(self, attributes: &[T]) -> Self
+        where
+            T: AsRef<str>,
+        {
+            let keywords = attributes
+                .iter()
+                .map(|a| IppValue::Keyword(a.as_ref().to_owned()))
+                .collect();
+            self.operation_attribute(IppAttribute::new(
+                JOB_MANDATORY_ATTRIBUTES,
+                IppValue::ListOf(keywords),
+            ))
+        }
+    };
+}
+
+// setters of document operation attributes for builders with an `operation_attribute` method
+macro_rules! document_methods {
+    () => {
+        /// Specify document-format operation attribute, for example `application/pdf`
+        pub fn document_format(self, format: &str) -> Self {
+            self.operation_attribute(IppAttribute::new(
+                DOCUMENT_FORMAT,
+                IppValue::MimeMediaType(format.to_owned()),
+            ))
+        }
+
+        /// Specify document-charset operation attribute, for example `utf-8`
+        pub fn document_charset(self, charset: &str) -> Self {
+            self.operation_attribute(IppAttribute::new(
+                DOCUMENT_CHARSET,
+                IppValue::Charset(charset.to_ascii_lowercase()),
+            ))
+        }
+
+        /// Specify document-natural-language operation attribute, for example `ja`
+        pub fn document_natural_language(self, language: &str) -> Self {
+            self.operation_attribute(IppAttribute::new(
+                DOCUMENT_NATURAL_LANGUAGE,
+                IppValue::NaturalLanguage(language.to_ascii_lowercase()),
+            ))
+        }
+    };
+}
+
 /// Builder to create IPP operations
 pub struct IppOperationBuilder;
 
 impl IppOperationBuilder {
     /// Create PrintJob operation
     ///
-    /// * `source` - `IppJobSource`
+    /// * `source` - `IppJobSource` or any `AsyncRead + Send + 'static`, for example a file
+    ///   or a document rendered into an in-memory cursor. Sources without a known length
+    ///   are streamed with chunked transfer encoding
     pub fn print_job<T>(source: T) -> PrintJobBuilder
     where
         IppJobSource: From<T>,
@@ -18,11 +234,35 @@ impl IppOperationBuilder {
         PrintJobBuilder::new(source.into())
     }
 
+    /// Create ValidateJob operation
+    ///
+    /// Accepts the same job attributes as `print_job` but carries no document. The response
+    /// status tells whether the printer would accept a Print-Job request with these attributes
+    pub fn validate_job() -> ValidateJobBuilder {
+        ValidateJobBuilder::new()
+    }
+
     /// Create GetPrinterAttributes operation
     pub fn get_printer_attributes() -> GetPrinterAttributesBuilder {
         GetPrinterAttributesBuilder::new()
     }
 
+    /// Create GetJobs operation
+    ///
+    /// Jobs are filtered with `GetJobs::which_jobs`: `not-completed` (default), `completed`,
+    /// `proof-print` for jobs waiting for proof approval. Use `groups_of(DelimiterTag::JobAttributes)`
+    /// to enumerate the returned jobs.
+    pub fn get_jobs() -> GetJobs {
+        GetJobs::new()
+    }
+
+    /// Create GetJobAttributes operation
+    ///
+    /// * `job_id` - id of the job to query. The job state is available via `IppAttributes::job_state`
+    pub fn get_job_attributes(job_id: i32) -> GetJobAttributes {
+        GetJobAttributes::new(job_id)
+    }
+
     /// Create CreateJob operation
     pub fn create_job() -> CreateJobBuilder {
         CreateJobBuilder::new()
@@ -31,13 +271,138 @@ impl IppOperationBuilder {
     /// Create SendDocument operation
     ///
     /// * `job_id` - job id returned by Create-Job operation <br/>
-    /// * `source` - `IppJobSource` <br/>
+    /// * `source` - `IppJobSource` or any `AsyncRead + Send + 'static`, streamed like in `print_job` <br/>
     pub fn send_document<T>(job_id: i32, source: T) -> SendDocumentBuilder
     where
         IppJobSource: From<T>,
     {
         SendDocumentBuilder::new(job_id, source.into())
     }
+
+    /// Create SendURI operation for pull printing.
+    ///
+    /// The printer fetches the document from `uri` itself, so the URI must be reachable from the printer
+    /// and use a scheme listed in its document-uri-schemes-supported attribute.
+    ///
+    /// * `job_id` - job id returned by Create-Job operation <br/>
+    /// * `uri` - document URI, for example `https://example.com/report.pdf` <br/>
+    pub fn send_uri(job_id: i32, uri: &str) -> SendUriBuilder {
+        SendUriBuilder::new(job_id, uri)
+    }
+
+    /// Create CancelJob operation
+    ///
+    /// * `job_id` - id of the job to cancel <br/>
+    ///
+    /// Use `CancelJob::user_name` to set requesting-user-name of the job owner.
+    pub fn cancel_job(job_id: i32) -> CancelJob {
+        CancelJob::new(job_id)
+    }
+
+    /// Create HoldJob operation which prevents a pending job from being scheduled
+    ///
+    /// * `job_id` - id of the job to hold
+    pub fn hold_job(job_id: i32) -> HoldJob {
+        HoldJob::new(job_id)
+    }
+
+    /// Create ReleaseJob operation which makes a held job a candidate for scheduling again
+    ///
+    /// * `job_id` - id of the held job
+    pub fn release_job(job_id: i32) -> ReleaseJob {
+        ReleaseJob::new(job_id)
+    }
+
+    /// Create PausePrinter operation
+    ///
+    /// This is an administrative operation, printers usually require an authenticated operator
+    /// and respond with `client-error-not-authorized` otherwise.
+    pub fn pause_printer() -> PausePrinter {
+        PausePrinter::new()
+    }
+
+    /// Create ResumePrinter operation
+    ///
+    /// This is an administrative operation, printers usually require an authenticated operator
+    /// and respond with `client-error-not-authorized` otherwise.
+    pub fn resume_printer() -> ResumePrinter {
+        ResumePrinter::new()
+    }
+
+    /// Create IdentifyPrinter operation which makes the printer beep or flash,
+    /// for example `identify_printer().actions(&["sound", "flash"])`
+    pub fn identify_printer() -> IdentifyPrinter {
+        IdentifyPrinter::new()
+    }
+
+    /// Create PurgeJobs operation which cancels all jobs of the printer, CUPS extension.
+    ///
+    /// This is an administrative operation, CUPS requires an authenticated operator
+    /// and responds with `client-error-not-authorized` otherwise.
+    #[cfg(feature = "cups")]
+    pub fn purge_jobs() -> crate::operation::cups::PurgeJobs {
+        crate::operation::cups::PurgeJobs::new()
+    }
+
+    /// Create CUPS-Get-Document operation which retrieves a stored job document, CUPS extension.
+    ///
+    /// * `job_id` - job ID<br/>
+    /// * `document_number` - 1-based number of the document within the job<br/>
+    ///
+    /// The document follows the response attributes as the payload.
+    #[cfg(feature = "cups")]
+    pub fn get_document(job_id: i32, document_number: i32) -> crate::operation::cups::CupsGetDocument {
+        crate::operation::cups::CupsGetDocument::new(job_id, document_number)
+    }
+
+    /// Create GetDocuments operation
+    ///
+    /// * `job_id` - id of the job to enumerate documents of <br/>
+    ///
+    /// Use `DocumentAttributes::from_attributes` to parse the response.
+    pub fn get_documents(job_id: i32) -> GetDocuments {
+        GetDocuments::new(job_id)
+    }
+
+    /// Create CreatePrinterSubscriptions operation with `ippget` pull delivery
+    ///
+    /// * `events` - notify-events keywords, for example `&["printer-state-changed"]` <br/>
+    /// * `lease_duration` - requested lease duration in seconds, 0 for no expiration <br/>
+    pub fn create_printer_subscriptions<T>(events: &[T], lease_duration: i32) -> CreatePrinterSubscriptions
+    where
+        T: AsRef<str>,
+    {
+        CreatePrinterSubscriptions::new(events, Some(lease_duration))
+    }
+
+    /// Create GetNotifications operation
+    ///
+    /// * `subscription_id` - id of the subscription to fetch events of <br/>
+    /// * `sequence_number` - lowest notify-sequence-number to return, all pending events if None <br/>
+    pub fn get_notifications(subscription_id: i32, sequence_number: Option<i32>) -> GetNotifications {
+        GetNotifications::new(subscription_id, sequence_number)
+    }
+
+    /// Create RenewSubscription operation
+    ///
+    /// * `subscription_id` - id of the subscription to renew <br/>
+    /// * `lease_duration` - requested lease duration in seconds <br/>
+    ///
+    /// If the subscription is unknown or already expired the printer responds with
+    /// `client-error-not-found` status.
+    pub fn renew_subscription(subscription_id: i32, lease_duration: i32) -> RenewSubscription {
+        RenewSubscription::new(subscription_id, Some(lease_duration))
+    }
+
+    /// Create CancelSubscription operation
+    ///
+    /// * `subscription_id` - id of the subscription to cancel <br/>
+    ///
+    /// If the subscription is unknown or already expired the printer responds with
+    /// `client-error-not-found` status.
+    pub fn cancel_subscription(subscription_id: i32) -> CancelSubscription {
+        CancelSubscription::new(subscription_id)
+    }
 }
 
 /// Builder to create PrintJob operation
@@ -45,7 +410,7 @@ pub struct PrintJobBuilder {
     source: IppJobSource,
     user_name: Option<String>,
     job_title: Option<String>,
-    attributes: Vec<IppAttribute>,
+    template: JobTemplate,
 }
 
 impl PrintJobBuilder {
@@ -54,7 +419,7 @@ impl PrintJobBuilder {
             source,
             user_name: None,
             job_title: None,
-            attributes: Vec::new(),
+            template: JobTemplate::default(),
         }
     }
     /// Specify requesting-user-name attribute
@@ -69,16 +434,72 @@ impl PrintJobBuilder {
         self
     }
 
-    /// Specify custom job attribute
-    pub fn attribute(mut self, attribute: IppAttribute) -> Self {
-        self.attributes.push(attribute);
+    job_template_methods!();
+
+    /// Compress the document while sending it and specify the compression operation attribute
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.source = self.source.compress(compression);
+        self.operation_attribute(IppAttribute::new(
+            COMPRESSION,
+            IppValue::Keyword(compression.as_keyword().to_owned()),
+        ))
+    }
+
+    document_methods!();
+
+    /// Build operation
+    pub fn build(self) -> impl IppOperation {
+        let (operation_attributes, attributes) = self.template.into_attributes();
+        let mut op = PrintJob::new(self.source, self.user_name.as_ref(), self.job_title.as_ref());
+        for attr in operation_attributes {
+            op.add_operation_attribute(attr);
+        }
+        attributes.into_iter().fold(op, |mut op, attr| {
+            op.add_attribute(attr);
+            op
+        })
+    }
+}
+
+/// Builder to create ValidateJob operation
+pub struct ValidateJobBuilder {
+    user_name: Option<String>,
+    job_title: Option<String>,
+    template: JobTemplate,
+}
+
+impl ValidateJobBuilder {
+    fn new() -> ValidateJobBuilder {
+        ValidateJobBuilder {
+            user_name: None,
+            job_title: None,
+            template: JobTemplate::default(),
+        }
+    }
+    /// Specify requesting-user-name attribute
+    pub fn user_name(mut self, user_name: &str) -> Self {
+        self.user_name = Some(user_name.to_owned());
         self
     }
 
+    /// Specify job-name attribute
+    pub fn job_title(mut self, job_title: &str) -> Self {
+        self.job_title = Some(job_title.to_owned());
+        self
+    }
+
+    job_template_methods!();
+
+    document_methods!();
+
     /// Build operation
     pub fn build(self) -> impl IppOperation {
-        let op = PrintJob::new(self.source, self.user_name.as_ref(), self.job_title.as_ref());
-        self.attributes.into_iter().fold(op, |mut op, attr| {
+        let (operation_attributes, attributes) = self.template.into_attributes();
+        let mut op = ValidateJob::new(self.user_name.as_ref(), self.job_title.as_ref());
+        for attr in operation_attributes {
+            op.add_operation_attribute(attr);
+        }
+        attributes.into_iter().fold(op, |mut op, attr| {
             op.add_attribute(attr);
             op
         })
@@ -101,13 +522,15 @@ impl GetPrinterAttributesBuilder {
         self
     }
 
-    /// Specify which attributes to retrieve from the printer
-    pub fn attributes<T>(mut self, attributes: &[T]) -> Self
+    /// Specify which attributes to retrieve from the printer.
+    /// All of them are sent as a single multi-valued requested-attributes attribute
+    pub fn attributes<I>(mut self, attributes: I) -> Self
     where
-        T: AsRef<str>,
+        I: IntoIterator,
+        I::Item: AsRef<str>,
     {
         self.attributes
-            .extend(attributes.iter().map(|s| s.as_ref().to_string()));
+            .extend(attributes.into_iter().map(|s| s.as_ref().to_string()));
         self
     }
 
@@ -120,14 +543,14 @@ impl GetPrinterAttributesBuilder {
 /// Builder to create CreateJob operation
 pub struct CreateJobBuilder {
     job_name: Option<String>,
-    attributes: Vec<IppAttribute>,
+    template: JobTemplate,
 }
 
 impl CreateJobBuilder {
     fn new() -> CreateJobBuilder {
         CreateJobBuilder {
             job_name: None,
-            attributes: Vec::new(),
+            template: JobTemplate::default(),
         }
     }
 
@@ -137,16 +560,16 @@ impl CreateJobBuilder {
         self
     }
 
-    /// Specify custom job attribute
-    pub fn attribute(mut self, attribute: IppAttribute) -> Self {
-        self.attributes.push(attribute);
-        self
-    }
+    job_template_methods!();
 
     /// Build operation
     pub fn build(self) -> impl IppOperation {
-        let op = CreateJob::new(self.job_name.as_ref());
-        self.attributes.into_iter().fold(op, |mut op, attr| {
+        let (operation_attributes, attributes) = self.template.into_attributes();
+        let mut op = CreateJob::new(self.job_name.as_ref());
+        for attr in operation_attributes {
+            op.add_operation_attribute(attr);
+        }
+        attributes.into_iter().fold(op, |mut op, attr| {
             op.add_attribute(attr);
             op
         })
@@ -159,6 +582,7 @@ pub struct SendDocumentBuilder {
     source: IppJobSource,
     user_name: Option<String>,
     is_last: bool,
+    operation_attributes: Vec<IppAttribute>,
 }
 
 impl SendDocumentBuilder {
@@ -168,6 +592,7 @@ impl SendDocumentBuilder {
             source,
             user_name: None,
             is_last: true,
+            operation_attributes: Vec::new(),
         }
     }
 
@@ -183,8 +608,679 @@ impl SendDocumentBuilder {
         self
     }
 
+    /// Specify custom operation attribute, for vendor or less common attributes without a dedicated method
+    pub fn operation_attribute(mut self, attribute: IppAttribute) -> Self {
+        self.operation_attributes.push(attribute);
+        self
+    }
+
+    /// Compress the document while sending it and specify the compression operation attribute
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.source = self.source.compress(compression);
+        self.operation_attributes.push(IppAttribute::new(
+            COMPRESSION,
+            IppValue::Keyword(compression.as_keyword().to_owned()),
+        ));
+        self
+    }
+
+    document_methods!();
+
     /// Build operation
     pub fn build(self) -> impl IppOperation {
-        SendDocument::new(self.job_id, self.source, self.user_name.as_ref(), self.is_last)
+        let mut op = SendDocument::new(self.job_id, self.source, self.user_name.as_ref(), self.is_last);
+        for attr in self.operation_attributes {
+            op.add_operation_attribute(attr);
+        }
+        op
+    }
+}
+
+/// Builder to create SendURI operation
+pub struct SendUriBuilder {
+    job_id: i32,
+    uri: String,
+    user_name: Option<String>,
+    is_last: bool,
+    operation_attributes: Vec<IppAttribute>,
+}
+
+impl SendUriBuilder {
+    fn new(job_id: i32, uri: &str) -> SendUriBuilder {
+        SendUriBuilder {
+            job_id,
+            uri: uri.to_owned(),
+            user_name: None,
+            is_last: true,
+            operation_attributes: Vec::new(),
+        }
+    }
+
+    /// Specify originating-user-name attribute
+    pub fn user_name(mut self, user_name: &str) -> Self {
+        self.user_name = Some(user_name.to_owned());
+        self
+    }
+
+    /// Parameter which indicates whether this document is a last one
+    pub fn last(mut self, last: bool) -> Self {
+        self.is_last = last;
+        self
+    }
+
+    /// Specify custom operation attribute, for vendor or less common attributes without a dedicated method
+    pub fn operation_attribute(mut self, attribute: IppAttribute) -> Self {
+        self.operation_attributes.push(attribute);
+        self
+    }
+
+    document_methods!();
+
+    /// Build operation
+    pub fn build(self) -> impl IppOperation {
+        let mut op = SendUri::new(self.job_id, &self.uri, self.user_name.as_ref(), self.is_last);
+        for attr in self.operation_attributes {
+            op.add_operation_attribute(attr);
+        }
+        op
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+    use crate::{
+        attribute::{
+            DOCUMENT_URI, IDENTIFY_ACTIONS, JOB_CREATION_ATTRIBUTES_SUPPORTED, JOB_HOLD_UNTIL, JOB_ID, LAST_DOCUMENT,
+            LIMIT, MESSAGE, MY_JOBS, NOTIFY_LEASE_DURATION, NOTIFY_SUBSCRIPTION_ID, PRINTER_URI,
+            PRINT_CONTENT_OPTIMIZE_SUPPORTED, PRINT_QUALITY_SUPPORTED, REQUESTED_ATTRIBUTES, REQUESTING_USER_NAME,
+            WHICH_JOBS,
+        },
+        ipp::DelimiterTag,
+        model::SeparatorSheetsType,
+        request::IppRequestResponse,
+    };
+
+    #[test]
+    fn test_mandatory_attributes() {
+        let request = IppOperationBuilder::create_job()
+            .mandatory_attributes(&["sides", "media"])
+            .build()
+            .into_ipp_request("ipp://localhost");
+
+        let attr = request
+            .attributes()
+            .groups_of(DelimiterTag::OperationAttributes)
+            .first()
+            .and_then(|g| g.attributes().get(JOB_MANDATORY_ATTRIBUTES))
+            .cloned();
+        assert_eq!(
+            attr.map(|a| a.value().clone()),
+            Some(IppValue::ListOf(vec![
+                IppValue::Keyword("sides".to_owned()),
+                IppValue::Keyword("media".to_owned()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_multi_document_job_attributes() {
+        let sheets = SeparatorSheets::new(SeparatorSheetsType::BothSheets);
+        let request = IppOperationBuilder::create_job()
+            .separator_sheets(&sheets)
+            .multiple_document_handling(MultipleDocumentHandling::SeparateDocumentsCollatedCopies)
+            .content_optimize(ContentOptimize::TextAndGraphic)
+            .build()
+            .into_ipp_request("ipp://localhost");
+
+        let group = &request.attributes().groups_of(DelimiterTag::JobAttributes)[0];
+        assert_eq!(
+            group.attributes().get(SEPARATOR_SHEETS).map(|a| a.value()),
+            Some(&sheets.to_value())
+        );
+        assert_eq!(
+            group.attributes().get(MULTIPLE_DOCUMENT_HANDLING).map(|a| a.value()),
+            Some(&IppValue::Keyword("separate-documents-collated-copies".to_owned()))
+        );
+        assert_eq!(
+            group.attributes().get(PRINT_CONTENT_OPTIMIZE).map(|a| a.value()),
+            Some(&IppValue::Keyword("text-and-graphic".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_job_template_builders() {
+        let media = MediaCol::from_media_name("iso_a4_210x297mm");
+        let job_attributes = |request: IppRequestResponse| {
+            let group = &request.attributes().groups_of(DelimiterTag::JobAttributes)[0];
+            let mut names = group.attributes().keys().cloned().collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+        let expected = vec![MEDIA_COL, PRINT_QUALITY, PROOF_PRINT];
+
+        let print_job = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .media_col(&media)
+            .print_quality(PrintQuality::Draft)
+            .proof_print(1)
+            .build();
+        assert_eq!(job_attributes(print_job.into_ipp_request("ipp://localhost")), expected);
+
+        let validate_job = IppOperationBuilder::validate_job()
+            .media_col(&media)
+            .print_quality(PrintQuality::Draft)
+            .proof_print(1)
+            .build();
+        assert_eq!(
+            job_attributes(validate_job.into_ipp_request("ipp://localhost")),
+            expected
+        );
+
+        let create_job = IppOperationBuilder::create_job()
+            .media_col(&media)
+            .print_quality(PrintQuality::Draft)
+            .proof_print(1)
+            .build();
+        assert_eq!(job_attributes(create_job.into_ipp_request("ipp://localhost")), expected);
+    }
+
+    #[test]
+    fn test_proof_print() {
+        let request = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .proof_print(1)
+            .build()
+            .into_ipp_request("ipp://localhost");
+
+        let group = &request.attributes().groups_of(DelimiterTag::JobAttributes)[0];
+        assert_eq!(
+            group.attributes().get(PROOF_PRINT).map(|a| a.value()),
+            Some(&IppValue::collection(vec![(PROOF_PRINT_COPIES, IppValue::Integer(1))]))
+        );
+
+        let request = IppOperationBuilder::get_jobs()
+            .which_jobs("proof-print")
+            .into_ipp_request("ipp://localhost");
+        let group = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            group.attributes().get(WHICH_JOBS).map(|a| a.value()),
+            Some(&IppValue::Keyword("proof-print".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_check_capabilities() {
+        let mut attrs = crate::IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                JOB_CREATION_ATTRIBUTES_SUPPORTED,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("copies".to_owned()),
+                    IppValue::Keyword("print-quality".to_owned()),
+                ]),
+            ),
+        );
+        let caps = PrinterCapabilities::from(attrs);
+
+        let builder = IppOperationBuilder::create_job()
+            .check_capabilities(&caps)
+            .print_quality(PrintQuality::High)
+            .multiple_document_handling(MultipleDocumentHandling::SingleDocument);
+        assert_eq!(builder.unsupported_attributes(), vec![MULTIPLE_DOCUMENT_HANDLING]);
+
+        let builder = IppOperationBuilder::create_job()
+            .check_capabilities(&PrinterCapabilities::default())
+            .multiple_document_handling(MultipleDocumentHandling::SingleDocument);
+        assert!(builder.unsupported_attributes().is_empty());
+
+        let mut attrs = crate::IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINT_QUALITY_SUPPORTED,
+                IppValue::ListOf(vec![
+                    IppValue::Enum(PrintQuality::Draft as i32),
+                    IppValue::Enum(PrintQuality::Normal as i32),
+                ]),
+            ),
+        );
+        let caps = PrinterCapabilities::from(attrs);
+        let builder = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .check_capabilities(&caps)
+            .print_quality(PrintQuality::High);
+        assert_eq!(builder.unsupported_attributes(), vec![PRINT_QUALITY]);
+        let builder = IppOperationBuilder::validate_job()
+            .check_capabilities(&caps)
+            .print_quality(PrintQuality::Normal)
+            .multiple_document_handling(MultipleDocumentHandling::SingleDocument);
+        assert!(builder.unsupported_attributes().is_empty());
+
+        let mut attrs = crate::IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINT_CONTENT_OPTIMIZE_SUPPORTED,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("auto".to_owned()),
+                    IppValue::Keyword("photo".to_owned()),
+                ]),
+            ),
+        );
+        let caps = PrinterCapabilities::from(attrs);
+        let builder = IppOperationBuilder::create_job()
+            .check_capabilities(&caps)
+            .content_optimize(ContentOptimize::Text);
+        assert_eq!(builder.unsupported_attributes(), vec![PRINT_CONTENT_OPTIMIZE]);
+        let builder = IppOperationBuilder::create_job()
+            .check_capabilities(&caps)
+            .content_optimize(ContentOptimize::Photo);
+        assert!(builder.unsupported_attributes().is_empty());
+    }
+
+    #[test]
+    fn test_document_charset_and_language() {
+        let request = IppOperationBuilder::send_document(1, io::Cursor::new(Vec::new()))
+            .document_charset("UTF-8")
+            .document_natural_language("ja")
+            .build()
+            .into_ipp_request("ipp://localhost");
+
+        let group = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            group.attributes().get(DOCUMENT_CHARSET).map(|a| a.value()),
+            Some(&IppValue::Charset("utf-8".to_owned()))
+        );
+        assert_eq!(
+            group.attributes().get(DOCUMENT_NATURAL_LANGUAGE).map(|a| a.value()),
+            Some(&IppValue::NaturalLanguage("ja".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_renew_subscription() {
+        let request = IppOperationBuilder::renew_subscription(42, 3600).into_ipp_request("ipp://localhost");
+        assert_eq!(request.header().operation_status, 0x1a);
+
+        let attributes = request.attributes();
+        let op = &attributes.groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(NOTIFY_SUBSCRIPTION_ID).map(|a| a.value()),
+            Some(&IppValue::Integer(42))
+        );
+        let sub = &attributes.groups_of(DelimiterTag::SubscriptionAttributes)[0];
+        assert_eq!(
+            sub.attributes().get(NOTIFY_LEASE_DURATION).map(|a| a.value()),
+            Some(&IppValue::Integer(3600))
+        );
+    }
+
+    #[test]
+    fn test_cancel_job() {
+        let request = IppOperationBuilder::cancel_job(7)
+            .user_name("alice")
+            .into_ipp_request("ipp://localhost");
+        assert_eq!(request.header().operation_status, 0x08);
+
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(JOB_ID).map(|a| a.value()),
+            Some(&IppValue::Integer(7))
+        );
+        assert_eq!(
+            op.attributes().get(REQUESTING_USER_NAME).map(|a| a.value()),
+            Some(&IppValue::NameWithoutLanguage("alice".to_owned()))
+        );
+        assert!(request.attributes().groups_of(DelimiterTag::JobAttributes).is_empty());
+    }
+
+    #[test]
+    fn test_validate_job() {
+        let request = IppOperationBuilder::validate_job()
+            .user_name("alice")
+            .document_format("application/pdf")
+            .print_quality(PrintQuality::High)
+            .build()
+            .into_ipp_request("ipp://localhost");
+        assert_eq!(request.header().operation_status, 0x04);
+        assert!(request.payload().is_none());
+
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(DOCUMENT_FORMAT).map(|a| a.value()),
+            Some(&IppValue::MimeMediaType("application/pdf".to_owned()))
+        );
+        let job = &request.attributes().groups_of(DelimiterTag::JobAttributes)[0];
+        assert_eq!(
+            job.attributes().get(PRINT_QUALITY).map(|a| a.value()),
+            Some(&IppValue::Enum(PrintQuality::High as i32))
+        );
+        assert!(request
+            .attributes()
+            .groups_of(DelimiterTag::DocumentAttributes)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_get_job_attributes() {
+        let request = IppOperationBuilder::get_job_attributes(42)
+            .attributes(&["job-state", "job-state-reasons"])
+            .into_ipp_request("ipp://localhost");
+        assert_eq!(request.header().operation_status, 0x09);
+
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(PRINTER_URI).map(|a| a.value()),
+            Some(&IppValue::Uri("ipp://localhost".to_owned()))
+        );
+        assert_eq!(
+            op.attributes().get(JOB_ID).map(|a| a.value()),
+            Some(&IppValue::Integer(42))
+        );
+        assert_eq!(
+            op.attributes().get(REQUESTED_ATTRIBUTES).map(|a| a.value()),
+            Some(&IppValue::ListOf(vec![
+                IppValue::Keyword("job-state".to_owned()),
+                IppValue::Keyword("job-state-reasons".to_owned())
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_send_uri() {
+        let request = IppOperationBuilder::send_uri(3, "https://example.com/report.pdf")
+            .last(false)
+            .document_format("application/pdf")
+            .build()
+            .into_ipp_request("ipp://localhost");
+        assert_eq!(request.header().operation_status, 0x07);
+        assert!(request.payload().is_none());
+
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(JOB_ID).map(|a| a.value()),
+            Some(&IppValue::Integer(3))
+        );
+        assert_eq!(
+            op.attributes().get(DOCUMENT_URI).map(|a| a.value()),
+            Some(&IppValue::Uri("https://example.com/report.pdf".to_owned()))
+        );
+        assert_eq!(
+            op.attributes().get(LAST_DOCUMENT).map(|a| a.value()),
+            Some(&IppValue::Boolean(false))
+        );
+
+        let request = IppOperationBuilder::send_uri(3, "https://example.com/report.pdf")
+            .build()
+            .into_ipp_request("ipp://localhost");
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(LAST_DOCUMENT).map(|a| a.value()),
+            Some(&IppValue::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_hold_release_job() {
+        let request = IppOperationBuilder::hold_job(5)
+            .user_name("alice")
+            .hold_until("night")
+            .into_ipp_request("ipp://localhost");
+        assert_eq!(request.header().operation_status, 0x0c);
+
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(PRINTER_URI).map(|a| a.value()),
+            Some(&IppValue::Uri("ipp://localhost".to_owned()))
+        );
+        assert_eq!(
+            op.attributes().get(JOB_ID).map(|a| a.value()),
+            Some(&IppValue::Integer(5))
+        );
+        assert_eq!(
+            op.attributes().get(REQUESTING_USER_NAME).map(|a| a.value()),
+            Some(&IppValue::NameWithoutLanguage("alice".to_owned()))
+        );
+        assert_eq!(
+            op.attributes().get(JOB_HOLD_UNTIL).map(|a| a.value()),
+            Some(&IppValue::Keyword("night".to_owned()))
+        );
+
+        let request = IppOperationBuilder::release_job(5).into_ipp_request("ipp://localhost");
+        assert_eq!(request.header().operation_status, 0x0d);
+
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(JOB_ID).map(|a| a.value()),
+            Some(&IppValue::Integer(5))
+        );
+        assert!(op.attributes().get(REQUESTING_USER_NAME).is_none());
+    }
+
+    #[test]
+    fn test_get_jobs_filter() {
+        let mut request = IppOperationBuilder::get_jobs()
+            .which_jobs("completed")
+            .my_jobs(true)
+            .limit(10)
+            .into_ipp_request("ipp://localhost");
+        assert_eq!(request.header().operation_status, 0x0a);
+
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(WHICH_JOBS).map(|a| a.value()),
+            Some(&IppValue::Keyword("completed".to_owned()))
+        );
+        assert_eq!(
+            op.attributes().get(MY_JOBS).map(|a| a.value()),
+            Some(&IppValue::Boolean(true))
+        );
+        assert_eq!(
+            op.attributes().get(LIMIT).map(|a| a.value()),
+            Some(&IppValue::Integer(10))
+        );
+
+        let mut buf = Vec::new();
+        request.write(&mut buf).unwrap();
+        let limit = [
+            &[0x21, 0x00, 0x05][..],
+            b"limit",
+            &[0x00, 0x04, 0x00, 0x00, 0x00, 0x0a][..],
+        ]
+        .concat();
+        assert!(buf.windows(limit.len()).any(|w| w == &limit[..]));
+    }
+
+    #[test]
+    fn test_pause_resume_printer() {
+        let mut request = IppOperationBuilder::pause_printer()
+            .user_name("admin")
+            .into_ipp_request("ipp://localhost");
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(REQUESTING_USER_NAME).map(|a| a.value()),
+            Some(&IppValue::NameWithoutLanguage("admin".to_owned()))
+        );
+        let mut buf = Vec::new();
+        request.write(&mut buf).unwrap();
+        assert_eq!(&buf[2..4], &[0x00, 0x10]);
+
+        let mut request = IppOperationBuilder::resume_printer().into_ipp_request("ipp://localhost");
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert!(op.attributes().get(REQUESTING_USER_NAME).is_none());
+        let mut buf = Vec::new();
+        request.write(&mut buf).unwrap();
+        assert_eq!(&buf[2..4], &[0x00, 0x11]);
+    }
+
+    #[test]
+    fn test_identify_printer() {
+        let mut request = IppOperationBuilder::identify_printer()
+            .actions(&["sound", "flash"])
+            .message("Here")
+            .into_ipp_request("ipp://localhost");
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(MESSAGE).map(|a| a.value()),
+            Some(&IppValue::TextWithoutLanguage("Here".to_owned()))
+        );
+
+        let mut buf = Vec::new();
+        request.write(&mut buf).unwrap();
+        assert_eq!(&buf[0..4], &[0x02, 0x00, 0x00, 0x3C]);
+
+        // 1setOf keyword: the second value has an empty name
+        let mut actions = vec![0x44, 0x00, 0x10];
+        actions.extend_from_slice(b"identify-actions");
+        actions.extend_from_slice(&[0x00, 0x05]);
+        actions.extend_from_slice(b"sound");
+        actions.extend_from_slice(&[0x44, 0x00, 0x00, 0x00, 0x05]);
+        actions.extend_from_slice(b"flash");
+        assert!(buf.windows(actions.len()).any(|w| w == &actions[..]));
+
+        let request = IppOperationBuilder::identify_printer().into_ipp_request("ipp://localhost");
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert!(op.attributes().get(IDENTIFY_ACTIONS).is_none());
+    }
+
+    #[test]
+    fn test_compression() {
+        use flate2::read::GzDecoder;
+        use futures::{Future, Stream};
+        use std::io::Read;
+
+        let document = b"%!PS-Adobe-3.0\n".repeat(1000);
+        let request = IppOperationBuilder::print_job(io::Cursor::new(document.clone()))
+            .compression(Compression::Gzip)
+            .build()
+            .into_ipp_request("ipp://localhost");
+        assert_eq!(request.content_length(), None);
+
+        let data = request.into_stream().concat2().wait().unwrap();
+        let parsed = IppRequestResponse::from_bytes(&data).unwrap();
+        let op = &parsed.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(COMPRESSION).map(|a| a.value()),
+            Some(&IppValue::Keyword("gzip".to_owned()))
+        );
+
+        let payload = parsed.payload_bytes().unwrap();
+        assert!(payload.len() < document.len());
+        let mut decoded = Vec::new();
+        GzDecoder::new(payload).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, document);
+
+        let request = IppOperationBuilder::send_document(1, io::Cursor::new(document.clone()))
+            .compression(Compression::Deflate)
+            .build()
+            .into_ipp_request("ipp://localhost");
+        let data = request.into_stream().concat2().wait().unwrap();
+        let parsed = IppRequestResponse::from_bytes(&data).unwrap();
+        let mut decoded = Vec::new();
+        flate2::read::DeflateDecoder::new(parsed.payload_bytes().unwrap())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, document);
+    }
+
+    #[test]
+    fn test_custom_attributes() {
+        let request = IppOperationBuilder::print_job(io::Cursor::new(Vec::new()))
+            .operation_attribute(IppAttribute::new(
+                "x-vendor-mode",
+                IppValue::Keyword("draft".to_owned()),
+            ))
+            .job_attribute(IppAttribute::new(
+                "job-sheets",
+                IppValue::Keyword("standard".to_owned()),
+            ))
+            .build()
+            .into_ipp_request("ipp://localhost");
+
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get("x-vendor-mode").map(|a| a.value()),
+            Some(&IppValue::Keyword("draft".to_owned()))
+        );
+        let job = &request.attributes().groups_of(DelimiterTag::JobAttributes)[0];
+        assert_eq!(
+            job.attributes().get("job-sheets").map(|a| a.value()),
+            Some(&IppValue::Keyword("standard".to_owned()))
+        );
+        assert!(op.attributes().get("job-sheets").is_none());
+
+        let mut request = IppOperationBuilder::send_document(1, io::Cursor::new(Vec::new()))
+            .operation_attribute(IppAttribute::new(
+                "x-vendor-mode",
+                IppValue::Keyword("draft".to_owned()),
+            ))
+            .build()
+            .into_ipp_request("ipp://localhost");
+        let mut buf = Vec::new();
+        request.write(&mut buf).unwrap();
+        assert!(buf.windows(b"x-vendor-mode".len()).any(|w| w == b"x-vendor-mode"));
+    }
+
+    #[cfg(feature = "cups")]
+    #[test]
+    fn test_purge_jobs() {
+        let mut request = IppOperationBuilder::purge_jobs()
+            .user_name("admin")
+            .into_ipp_request("ipp://localhost");
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(PRINTER_URI).map(|a| a.value()),
+            Some(&IppValue::Uri("ipp://localhost".to_owned()))
+        );
+        assert_eq!(
+            op.attributes().get(REQUESTING_USER_NAME).map(|a| a.value()),
+            Some(&IppValue::NameWithoutLanguage("admin".to_owned()))
+        );
+        let mut buf = Vec::new();
+        request.write(&mut buf).unwrap();
+        assert_eq!(&buf[2..4], &[0x00, 0x12]);
+    }
+
+    #[cfg(feature = "cups")]
+    #[test]
+    fn test_get_document() {
+        let mut request = IppOperationBuilder::get_document(7, 2).into_ipp_request("ipp://localhost");
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(JOB_ID).map(|a| a.value()),
+            Some(&IppValue::Integer(7))
+        );
+        assert_eq!(
+            op.attributes()
+                .get(crate::attribute::DOCUMENT_NUMBER)
+                .map(|a| a.value()),
+            Some(&IppValue::Integer(2))
+        );
+        let mut buf = Vec::new();
+        request.write(&mut buf).unwrap();
+        assert_eq!(&buf[2..4], &[0x40, 0x27]);
+    }
+
+    #[test]
+    fn test_requested_attributes() {
+        let names = ["printer-state", "printer-state-reasons", "media-ready"];
+        let mut request = IppOperationBuilder::get_printer_attributes()
+            .attributes(names.iter().copied())
+            .build()
+            .into_ipp_request("ipp://localhost");
+
+        let op = &request.attributes().groups_of(DelimiterTag::OperationAttributes)[0];
+        assert_eq!(
+            op.attributes().get(REQUESTED_ATTRIBUTES).map(|a| a.value()),
+            Some(&IppValue::ListOf(
+                names.iter().map(|n| IppValue::Keyword(n.to_string())).collect()
+            ))
+        );
+
+        let mut buf = Vec::new();
+        request.write(&mut buf).unwrap();
+        let name = REQUESTED_ATTRIBUTES.as_bytes();
+        assert_eq!(buf.windows(name.len()).filter(|w| *w == name).count(), 1);
     }
 }
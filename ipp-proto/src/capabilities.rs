@@ -0,0 +1,733 @@
+//!
+//! Typed view over printer description attributes
+//!
+use std::collections::HashMap;
+
+use num_traits::FromPrimitive;
+
+use crate::{
+    attribute::*,
+    ipp::{DelimiterTag, PrintQuality},
+    model::{
+        AuthMethod, ContentOptimize, DeviceId, MediaCol, PrinterAlert, PrinterKind, PrinterStateReason, ReasonSeverity,
+        Supply,
+    },
+    IppAttribute, IppAttributes, IppValue,
+};
+
+// check a single value against a single value of xxx-supported attribute
+fn value_supported(value: &IppValue, supported: &IppValue) -> bool {
+    match (value, supported) {
+        (IppValue::Integer(i), IppValue::RangeOfInteger { min, max }) => i >= min && i <= max,
+        (_, IppValue::Boolean(b)) => *b,
+        (value, supported) => value == supported,
+    }
+}
+
+// parse latitude and longitude from RFC 5870 geo URI, for example `geo:52.52,13.40,35;u=10`
+fn parse_geo_uri(uri: &str) -> Option<(f64, f64)> {
+    if !uri.get(..4)?.eq_ignore_ascii_case("geo:") {
+        return None;
+    }
+    let coords = uri[4..].split(';').next()?;
+    let mut parts = coords.split(',');
+    let lat = parts.next()?.trim().parse::<f64>().ok()?;
+    let long = parts.next()?.trim().parse::<f64>().ok()?;
+
+    if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&long) {
+        Some((lat, long))
+    } else {
+        None
+    }
+}
+
+/// Printer capabilities as returned by Get-Printer-Attributes operation
+#[derive(Clone, Debug, Default)]
+pub struct PrinterCapabilities {
+    attributes: HashMap<String, IppAttribute>,
+}
+
+impl PrinterCapabilities {
+    /// Create capabilities from the first printer attributes group of the response
+    pub fn from_attributes(attributes: &IppAttributes) -> PrinterCapabilities {
+        PrinterCapabilities {
+            attributes: attributes
+                .groups_of(DelimiterTag::PrinterAttributes)
+                .first()
+                .map(|g| g.attributes().clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Get all printer attributes
+    pub fn attributes(&self) -> &HashMap<String, IppAttribute> {
+        &self.attributes
+    }
+
+    /// Get printer attribute by name
+    pub fn attribute(&self, name: &str) -> Option<&IppAttribute> {
+        self.attributes.get(name)
+    }
+
+    /// Check whether a job attribute value is supported according to the corresponding xxx-supported attribute.
+    ///
+    /// Returns None if the printer does not report supported values for this attribute.
+    /// For collection values the member names are checked against the list of supported members.
+    pub fn supports(&self, attribute: &IppAttribute) -> Option<bool> {
+        let supported = self.attribute(&format!("{}-supported", attribute.name()))?.value();
+
+        let is_supported = |value: &IppValue| match value {
+            IppValue::Collection(ref members) => members
+                .iter()
+                .filter(|m| m.as_memberattrname().is_some())
+                .all(|m| supported.into_iter().any(|s| s.as_keyword() == m.as_memberattrname())),
+            _ => supported.into_iter().any(|s| value_supported(value, s)),
+        };
+
+        Some(match attribute.value() {
+            IppValue::ListOf(ref list) => list.iter().all(is_supported),
+            value => is_supported(value),
+        })
+    }
+
+    fn text(&self, name: &str) -> Option<&str> {
+        self.attribute(name).and_then(|attr| attr.value().as_str())
+    }
+
+    fn text_list(&self, name: &str) -> Vec<&str> {
+        self.attribute(name)
+            .map(|attr| attr.value().as_string_list())
+            .unwrap_or_default()
+    }
+
+    /// Latest of printer-config-change-time and printer-state-change-time, in seconds of printer-up-time
+    pub fn change_time(&self) -> Option<i32> {
+        [PRINTER_CONFIG_CHANGE_TIME, PRINTER_STATE_CHANGE_TIME]
+            .iter()
+            .filter_map(|name| self.attribute(name).and_then(|attr| attr.value().as_integer()))
+            .max()
+            .cloned()
+    }
+
+    /// Printer make and model (printer-make-and-model)
+    pub fn make_and_model(&self) -> Option<&str> {
+        self.text(PRINTER_MAKE_AND_MODEL)
+    }
+
+    /// Parsed IEEE 1284 device ID (printer-device-id)
+    pub fn device_id(&self) -> Option<DeviceId> {
+        self.text(PRINTER_DEVICE_ID).map(DeviceId::parse)
+    }
+
+    /// Printer serial number as reported in the device ID
+    pub fn serial_number(&self) -> Option<String> {
+        self.device_id()
+            .and_then(|id| id.serial_number().map(ToOwned::to_owned))
+    }
+
+    /// Version of the main firmware component (first value of printer-firmware-string-version)
+    pub fn firmware_version(&self) -> Option<&str> {
+        self.text_list(PRINTER_FIRMWARE_STRING_VERSION).first().cloned()
+    }
+
+    /// Name of the main firmware component (first value of printer-firmware-name)
+    pub fn firmware_name(&self) -> Option<&str> {
+        self.text_list(PRINTER_FIRMWARE_NAME).first().cloned()
+    }
+
+    /// Patches applied to the main firmware component (first value of printer-firmware-patches)
+    pub fn firmware_patches(&self) -> Option<&str> {
+        self.text_list(PRINTER_FIRMWARE_PATCHES).first().cloned()
+    }
+
+    /// All firmware components as (name, version) pairs. The attributes are parallel lists,
+    /// so the n-th name belongs to the n-th version
+    pub fn firmware(&self) -> Vec<(&str, &str)> {
+        self.text_list(PRINTER_FIRMWARE_NAME)
+            .into_iter()
+            .zip(self.text_list(PRINTER_FIRMWARE_STRING_VERSION))
+            .collect()
+    }
+
+    /// Printer location description (printer-location)
+    pub fn location(&self) -> Option<&str> {
+        self.text(PRINTER_LOCATION)
+    }
+
+    /// Printer latitude and longitude parsed from the geo URI in printer-geo-location
+    pub fn geo_location(&self) -> Option<(f64, f64)> {
+        self.text(PRINTER_GEO_LOCATION).and_then(parse_geo_uri)
+    }
+
+    /// Supported IPP features (ipp-features-supported), for example `ipp-everywhere`
+    pub fn supported_features(&self) -> Vec<String> {
+        self.text_list(IPP_FEATURES_SUPPORTED)
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect()
+    }
+
+    /// Check whether a given keyword is listed in ipp-features-supported
+    pub fn supports_feature(&self, feature: &str) -> bool {
+        self.text_list(IPP_FEATURES_SUPPORTED).contains(&feature)
+    }
+
+    /// Check whether the printer conforms to IPP Everywhere
+    pub fn supports_ipp_everywhere(&self) -> bool {
+        self.supports_feature("ipp-everywhere")
+    }
+
+    /// Check whether the printer supports document objects (Get-Documents and related operations)
+    pub fn supports_document_object(&self) -> bool {
+        self.supports_feature("document-object")
+    }
+
+    /// Check whether the printer supports subscription objects (event notifications)
+    pub fn supports_subscription_object(&self) -> bool {
+        self.supports_feature("subscription-object")
+    }
+
+    /// Printer supplies parsed from printer-supply, with descriptions from printer-supply-description
+    pub fn supplies(&self) -> Vec<Supply> {
+        let descriptions = self.text_list(PRINTER_SUPPLY_DESCRIPTION);
+        self.text_list(PRINTER_SUPPLY)
+            .into_iter()
+            .enumerate()
+            .map(|(i, s)| Supply {
+                description: descriptions.get(i).map(|d| (*d).to_owned()),
+                ..Supply::parse(s)
+            })
+            .collect()
+    }
+
+    /// Kinds of output produced by the printer (printer-kind)
+    pub fn printer_kinds(&self) -> Vec<PrinterKind> {
+        self.text_list(PRINTER_KIND)
+            .into_iter()
+            .map(PrinterKind::from_keyword)
+            .collect()
+    }
+
+    /// Whether the printer requires TLS: uri-security-supported is reported and none of its values is `none`
+    pub fn requires_tls(&self) -> bool {
+        let security = self.text_list(URI_SECURITY_SUPPORTED);
+        !security.is_empty() && security.iter().all(|s| *s != "none")
+    }
+
+    /// Authentication methods expected by the printer (uri-authentication-supported).
+    ///
+    /// Printers report one value per URI in printer-uri-supported, duplicates are removed
+    pub fn authentication_methods(&self) -> Vec<AuthMethod> {
+        let mut methods = Vec::new();
+        for method in self
+            .text_list(URI_AUTHENTICATION_SUPPORTED)
+            .into_iter()
+            .map(AuthMethod::from_keyword)
+        {
+            if !methods.contains(&method) {
+                methods.push(method);
+            }
+        }
+        methods
+    }
+
+    /// Parsed printer-state-reasons, excluding `none`
+    pub fn state_reasons(&self) -> Vec<PrinterStateReason> {
+        self.text_list(PRINTER_STATE_REASONS)
+            .into_iter()
+            .filter(|r| *r != "none")
+            .map(PrinterStateReason::parse)
+            .collect()
+    }
+
+    /// Printer state reasons split into (errors, warnings, reports)
+    pub fn state_reasons_by_severity(
+        &self,
+    ) -> (
+        Vec<PrinterStateReason>,
+        Vec<PrinterStateReason>,
+        Vec<PrinterStateReason>,
+    ) {
+        let mut result = (Vec::new(), Vec::new(), Vec::new());
+        for reason in self.state_reasons() {
+            match reason.severity {
+                ReasonSeverity::Error => result.0.push(reason),
+                ReasonSeverity::Warning => result.1.push(reason),
+                ReasonSeverity::Report => result.2.push(reason),
+            }
+        }
+        result
+    }
+
+    /// Printer alerts parsed from printer-alert, with descriptions from printer-alert-description.
+    ///
+    /// Duplicate entries are removed and the list is sorted by alert time, alerts without time come last
+    pub fn alerts(&self) -> Vec<PrinterAlert> {
+        let descriptions = self.text_list(PRINTER_ALERT_DESCRIPTION);
+        let mut alerts: Vec<PrinterAlert> = Vec::new();
+
+        for (i, alert) in self.text_list(PRINTER_ALERT).into_iter().enumerate() {
+            let alert = PrinterAlert {
+                description: descriptions.get(i).map(|d| (*d).to_owned()),
+                ..PrinterAlert::parse(alert)
+            };
+            if !alerts.contains(&alert) {
+                alerts.push(alert);
+            }
+        }
+
+        alerts.sort_by_key(|alert| (alert.time.is_none(), alert.time));
+        alerts
+    }
+
+    /// URI of the supply ordering or information page (printer-supply-info-uri)
+    pub fn supply_info_uri(&self) -> Option<&str> {
+        self.text(PRINTER_SUPPLY_INFO_URI)
+    }
+
+    /// Number of jobs queued on the printer (queued-job-count), a cheap measure of how busy it is
+    pub fn queued_job_count(&self) -> Option<i32> {
+        self.attribute(QUEUED_JOB_COUNT)
+            .and_then(|attr| attr.value().as_integer())
+            .cloned()
+    }
+
+    /// Supported rendering optimizations (print-content-optimize-supported). Unknown keywords are skipped
+    pub fn print_content_optimize_supported(&self) -> Vec<ContentOptimize> {
+        self.text_list(PRINT_CONTENT_OPTIMIZE_SUPPORTED)
+            .into_iter()
+            .filter_map(ContentOptimize::from_keyword)
+            .collect()
+    }
+
+    /// Currently loaded media with dimensions, margins, source and type (media-col-ready).
+    /// Entries which are not collections are skipped
+    pub fn media_col_ready(&self) -> Vec<MediaCol> {
+        // a single collection is not a list, iterating it would yield its members
+        match self.attribute(MEDIA_COL_READY).map(IppAttribute::value) {
+            Some(IppValue::ListOf(ref list)) => list.iter().filter_map(MediaCol::from_value).collect(),
+            Some(value @ IppValue::Collection(_)) => MediaCol::from_value(value).into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Names of the currently loaded media (media-ready), keywords or custom names
+    pub fn media_ready(&self) -> Vec<&str> {
+        self.text_list(MEDIA_READY)
+    }
+
+    /// Job template attributes accepted at job creation (job-creation-attributes-supported)
+    pub fn job_creation_attributes(&self) -> Vec<String> {
+        self.text_list(JOB_CREATION_ATTRIBUTES_SUPPORTED)
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect()
+    }
+
+    /// Supported print quality levels (print-quality-supported)
+    pub fn print_quality_supported(&self) -> Vec<PrintQuality> {
+        self.attribute(PRINT_QUALITY_SUPPORTED)
+            .map(|attr| {
+                attr.value()
+                    .into_iter()
+                    .filter_map(|v| v.as_enum())
+                    .filter_map(|v| PrintQuality::from_i32(*v))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl From<IppAttributes> for PrinterCapabilities {
+    fn from(attributes: IppAttributes) -> Self {
+        PrinterCapabilities::from_attributes(&attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_identification() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_MAKE_AND_MODEL,
+                IppValue::TextWithoutLanguage("HP LaserJet 4".to_owned()),
+            ),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_DEVICE_ID,
+                IppValue::TextWithoutLanguage("MFG:HP;MDL:LaserJet 4;SN:CN12345;".to_owned()),
+            ),
+        );
+
+        let caps = PrinterCapabilities::from(attrs);
+        assert_eq!(caps.make_and_model(), Some("HP LaserJet 4"));
+        assert_eq!(caps.device_id().unwrap().model(), Some("LaserJet 4"));
+        assert_eq!(caps.serial_number(), Some("CN12345".to_owned()));
+
+        let caps = PrinterCapabilities::from(IppAttributes::new());
+        assert_eq!(caps.make_and_model(), None);
+        assert_eq!(caps.device_id(), None);
+    }
+
+    #[test]
+    fn test_firmware() {
+        let mut attrs = IppAttributes::new();
+        let list = |values: &[&str]| {
+            IppValue::ListOf(
+                values
+                    .iter()
+                    .map(|v| IppValue::NameWithoutLanguage((*v).to_owned()))
+                    .collect(),
+            )
+        };
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_FIRMWARE_NAME, list(&["main", "network"])),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_FIRMWARE_STRING_VERSION, list(&["2.1.0", "1.4"])),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_FIRMWARE_PATCHES,
+                IppValue::TextWithoutLanguage("CVE-fix-1".to_owned()),
+            ),
+        );
+
+        let caps = PrinterCapabilities::from(attrs);
+        assert_eq!(caps.firmware_version(), Some("2.1.0"));
+        assert_eq!(caps.firmware_name(), Some("main"));
+        assert_eq!(caps.firmware_patches(), Some("CVE-fix-1"));
+        assert_eq!(caps.firmware(), vec![("main", "2.1.0"), ("network", "1.4")]);
+        assert_eq!(PrinterCapabilities::default().firmware_version(), None);
+    }
+
+    #[test]
+    fn test_print_content_optimize_supported() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINT_CONTENT_OPTIMIZE_SUPPORTED,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("auto".to_owned()),
+                    IppValue::Keyword("photo".to_owned()),
+                    IppValue::Keyword("vendor-special".to_owned()),
+                ]),
+            ),
+        );
+        let caps = PrinterCapabilities::from(attrs);
+        assert_eq!(
+            caps.print_content_optimize_supported(),
+            vec![ContentOptimize::Auto, ContentOptimize::Photo]
+        );
+
+        let photo = IppAttribute::new(PRINT_CONTENT_OPTIMIZE, IppValue::Keyword("photo".to_owned()));
+        assert_eq!(caps.supports(&photo), Some(true));
+        let text = IppAttribute::new(PRINT_CONTENT_OPTIMIZE, IppValue::Keyword("text".to_owned()));
+        assert_eq!(caps.supports(&text), Some(false));
+    }
+
+    #[test]
+    fn test_media_col_ready() {
+        let media = |name: &str, margin: i32| {
+            let mut media_col = MediaCol::from_media_name(name);
+            media_col.media_source = Some("main".to_owned());
+            media_col.media_bottom_margin = Some(margin);
+            media_col.media_left_margin = Some(margin);
+            media_col.media_right_margin = Some(margin);
+            media_col.media_top_margin = Some(margin);
+            media_col
+        };
+        let ready = vec![media("na_index-4x6_4x6in", 0), media("iso_a4_210x297mm", 300)];
+
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                MEDIA_COL_READY,
+                IppValue::ListOf(ready.iter().map(MediaCol::to_value).collect()),
+            ),
+        );
+
+        let media_col_ready = PrinterCapabilities::from(attrs).media_col_ready();
+        assert_eq!(media_col_ready, ready);
+        assert!(media_col_ready[0].is_borderless());
+        assert!(!media_col_ready[1].is_borderless());
+        assert!(!MediaCol::new().is_borderless());
+    }
+
+    #[test]
+    fn test_single_media_col_ready() {
+        let a4 = MediaCol::from_media_name("iso_a4_210x297mm");
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(MEDIA_COL_READY, a4.to_value()),
+        );
+
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(MEDIA_READY, IppValue::NameWithoutLanguage("custom-roll".to_owned())),
+        );
+
+        let capabilities = PrinterCapabilities::from(attrs);
+        assert_eq!(capabilities.media_col_ready(), vec![a4]);
+        assert_eq!(capabilities.media_ready(), vec!["custom-roll"]);
+        assert!(PrinterCapabilities::default().media_col_ready().is_empty());
+    }
+
+    #[test]
+    fn test_queued_job_count() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(QUEUED_JOB_COUNT, IppValue::Integer(3)),
+        );
+        assert_eq!(PrinterCapabilities::from(attrs).queued_job_count(), Some(3));
+        assert_eq!(PrinterCapabilities::default().queued_job_count(), None);
+    }
+
+    #[test]
+    fn test_location() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_LOCATION,
+                IppValue::TextWithoutLanguage("Bldg 4, Floor 2".to_owned()),
+            ),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_GEO_LOCATION,
+                IppValue::Uri("geo:52.52,-13.4,35;u=10".to_owned()),
+            ),
+        );
+
+        let caps = PrinterCapabilities::from(attrs);
+        assert_eq!(caps.location(), Some("Bldg 4, Floor 2"));
+        assert_eq!(caps.geo_location(), Some((52.52, -13.4)));
+
+        assert_eq!(parse_geo_uri("GEO:1,2"), Some((1.0, 2.0)));
+        assert_eq!(parse_geo_uri("geo:91,2"), None);
+        assert_eq!(parse_geo_uri("geo:1"), None);
+        assert_eq!(parse_geo_uri("http://example.com"), None);
+    }
+
+    #[test]
+    fn test_supported_features() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                IPP_FEATURES_SUPPORTED,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("ipp-everywhere".to_owned()),
+                    IppValue::Keyword("subscription-object".to_owned()),
+                ]),
+            ),
+        );
+
+        let caps = PrinterCapabilities::from(attrs);
+        assert_eq!(caps.supported_features(), vec!["ipp-everywhere", "subscription-object"]);
+        assert!(caps.supports_ipp_everywhere());
+        assert!(caps.supports_subscription_object());
+        assert!(!caps.supports_document_object());
+    }
+
+    #[test]
+    fn test_supplies() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_SUPPLY,
+                IppValue::ListOf(vec![
+                    IppValue::OctetString("index=1;type=toner;maxcapacity=100;level=80;colorantname=black;".to_owned()),
+                    IppValue::OctetString("index=2;type=toner;maxcapacity=100;level=5;colorantname=cyan;".to_owned()),
+                ]),
+            ),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_SUPPLY_DESCRIPTION,
+                IppValue::ListOf(vec![
+                    IppValue::TextWithoutLanguage("Black Toner".to_owned()),
+                    IppValue::TextWithoutLanguage("Cyan Toner".to_owned()),
+                ]),
+            ),
+        );
+
+        let supplies = PrinterCapabilities::from(attrs).supplies();
+        assert_eq!(supplies.len(), 2);
+        assert_eq!(supplies[0].description.as_deref(), Some("Black Toner"));
+        assert_eq!(supplies[0].level, Some(80));
+        assert_eq!(supplies[1].colorant_name.as_deref(), Some("cyan"));
+        assert_eq!(supplies[1].description.as_deref(), Some("Cyan Toner"));
+    }
+
+    #[test]
+    fn test_alerts() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_ALERT,
+                IppValue::ListOf(vec![
+                    IppValue::OctetString("code=mediaJam;severity=critical;group=mediaPath;time=500".to_owned()),
+                    IppValue::OctetString("code=coverOpen;severity=warning;group=cover".to_owned()),
+                    IppValue::OctetString("code=mediaJam;severity=critical;group=mediaPath;time=120".to_owned()),
+                    IppValue::OctetString("code=mediaJam;severity=critical;group=mediaPath;time=500".to_owned()),
+                ]),
+            ),
+        );
+
+        let alerts = PrinterCapabilities::from(attrs).alerts();
+        let times = alerts.iter().map(|a| a.time).collect::<Vec<_>>();
+        assert_eq!(times, vec![Some(120), Some(500), None]);
+        assert_eq!(alerts[2].code.as_deref(), Some("coverOpen"));
+    }
+
+    #[test]
+    fn test_state_reasons_by_severity() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_STATE_REASONS,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("media-jam-error".to_owned()),
+                    IppValue::Keyword("toner-low-warning".to_owned()),
+                    IppValue::Keyword("paused".to_owned()),
+                    IppValue::Keyword("other-report".to_owned()),
+                ]),
+            ),
+        );
+
+        let (errors, warnings, reports) = PrinterCapabilities::from(attrs).state_reasons_by_severity();
+        let names = |list: Vec<PrinterStateReason>| list.into_iter().map(|r| r.reason).collect::<Vec<_>>();
+        assert_eq!(names(errors), vec!["media-jam", "paused"]);
+        assert_eq!(names(warnings), vec!["toner-low"]);
+        assert_eq!(names(reports), vec!["other"]);
+
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE_REASONS, IppValue::Keyword("none".to_owned())),
+        );
+        assert!(PrinterCapabilities::from(attrs).state_reasons().is_empty());
+    }
+
+    #[test]
+    fn test_requires_tls() {
+        let caps = |values: Vec<&str>| {
+            let mut attrs = IppAttributes::new();
+            attrs.add(
+                DelimiterTag::PrinterAttributes,
+                IppAttribute::new(
+                    URI_SECURITY_SUPPORTED,
+                    IppValue::ListOf(values.into_iter().map(|v| IppValue::Keyword(v.to_owned())).collect()),
+                ),
+            );
+            PrinterCapabilities::from(attrs)
+        };
+        assert!(caps(vec!["tls"]).requires_tls());
+        assert!(!caps(vec!["none", "tls"]).requires_tls());
+        assert!(!PrinterCapabilities::default().requires_tls());
+    }
+
+    #[test]
+    fn test_authentication_methods() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                URI_AUTHENTICATION_SUPPORTED,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("requesting-user-name".to_owned()),
+                    IppValue::Keyword("basic".to_owned()),
+                    IppValue::Keyword("basic".to_owned()),
+                ]),
+            ),
+        );
+
+        let methods = PrinterCapabilities::from(attrs).authentication_methods();
+        assert_eq!(methods, vec![AuthMethod::RequestingUserName, AuthMethod::Basic]);
+        assert!(methods.iter().any(AuthMethod::requires_credentials));
+    }
+
+    #[test]
+    fn test_printer_kinds() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINTER_KIND,
+                IppValue::ListOf(vec![
+                    IppValue::Keyword("document".to_owned()),
+                    IppValue::Keyword("photo".to_owned()),
+                    IppValue::Keyword("x-vendor".to_owned()),
+                ]),
+            ),
+        );
+
+        assert_eq!(
+            PrinterCapabilities::from(attrs).printer_kinds(),
+            vec![
+                PrinterKind::Document,
+                PrinterKind::Photo,
+                PrinterKind::Other("x-vendor".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_print_quality_supported() {
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                PRINT_QUALITY_SUPPORTED,
+                IppValue::ListOf(vec![IppValue::Enum(3), IppValue::Enum(4), IppValue::Enum(42)]),
+            ),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(COPIES_SUPPORTED, IppValue::RangeOfInteger { min: 1, max: 99 }),
+        );
+
+        let caps = PrinterCapabilities::from(attrs);
+        assert_eq!(
+            caps.print_quality_supported(),
+            vec![PrintQuality::Draft, PrintQuality::Normal]
+        );
+
+        let quality = |q: PrintQuality| IppAttribute::new(PRINT_QUALITY, IppValue::Enum(q as i32));
+        assert_eq!(caps.supports(&quality(PrintQuality::Normal)), Some(true));
+        assert_eq!(caps.supports(&quality(PrintQuality::High)), Some(false));
+        assert_eq!(
+            caps.supports(&IppAttribute::new("copies", IppValue::Integer(5))),
+            Some(true)
+        );
+        assert_eq!(
+            caps.supports(&IppAttribute::new("copies", IppValue::Integer(100))),
+            Some(false)
+        );
+        assert_eq!(
+            caps.supports(&IppAttribute::new("sides", IppValue::Keyword("one-sided".to_owned()))),
+            None
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//!
+//! Annotated hex dump of serialized IPP data
+//!
+use std::io::{Cursor, Read};
+
+use byteorder::{BigEndian, ReadBytesExt};
+use num_traits::FromPrimitive;
+
+use crate::{
+    ipp::{DelimiterTag, Operation, StatusCode, ValueTag},
+    IppAttributes, IppHeader, IppValue, IppWriter,
+};
+
+const BYTES_PER_LINE: usize = 16;
+
+// best-effort description of the header fields and tags, keyed by offset
+fn annotate(data: &[u8], response: bool) -> Vec<(usize, String)> {
+    let mut result = Vec::new();
+    let mut reader = Cursor::new(data);
+
+    let mut header = || -> Option<()> {
+        let version = reader.read_u16::<BigEndian>().ok()?;
+        result.push((0, format!("version: {}.{}", version >> 8, version & 0xff)));
+
+        let code = reader.read_u16::<BigEndian>().ok()?;
+        // operation and status codes overlap, so the message kind decides how the code is read
+        let description = if response {
+            StatusCode::from_u16(code)
+                .map(|status| format!("status: {:?}", status))
+                .unwrap_or_else(|| format!("status: 0x{:04x}", code))
+        } else {
+            Operation::from_u16(code)
+                .map(|operation| format!("operation: {:?}", operation))
+                .unwrap_or_else(|| format!("operation: 0x{:04x}", code))
+        };
+        result.push((2, description));
+
+        let request_id = reader.read_u32::<BigEndian>().ok()?;
+        result.push((4, format!("request-id: {}", request_id)));
+        Some(())
+    };
+    if header().is_none() {
+        return result;
+    }
+
+    while let Ok(tag) = reader.read_u8() {
+        let offset = reader.position() as usize - 1;
+
+        if tag < 0x10 {
+            match DelimiterTag::from_u8(tag) {
+                Some(DelimiterTag::EndOfAttributes) => {
+                    result.push((offset, "end-of-attributes".to_owned()));
+                    if (reader.position() as usize) < data.len() {
+                        result.push((reader.position() as usize, "payload".to_owned()));
+                    }
+                    break;
+                }
+                Some(delimiter) => result.push((offset, format!("group: {:?}", delimiter))),
+                None => result.push((offset, format!("unknown delimiter: 0x{:02x}", tag))),
+            }
+            continue;
+        }
+
+        let mut value = || -> Option<String> {
+            let namelen = reader.read_u16::<BigEndian>().ok()?;
+            let mut name = vec![0; namelen as usize];
+            reader.read_exact(&mut name).ok()?;
+            let value = IppValue::read(tag, &mut reader).ok()?;
+
+            let kind = ValueTag::from_u8(tag)
+                .map(|t| format!("{:?}", t))
+                .unwrap_or_else(|| format!("0x{:02x}", tag));
+            let name = String::from_utf8_lossy(&name);
+
+            Some(if name.is_empty() {
+                format!("{}: {}", kind, value)
+            } else {
+                format!("{} {}: {}", kind, name, value)
+            })
+        };
+
+        match value() {
+            Some(description) => result.push((offset, description)),
+            None => {
+                result.push((offset, "truncated value".to_owned()));
+                break;
+            }
+        }
+    }
+    result
+}
+
+/// Produce annotated hex dump of the message header and attributes.
+///
+/// The retained message bytes are dumped up to the payload if available, so that the wire layout
+/// is kept. Otherwise the header and attributes are serialized, a serialization failure is noted
+/// at the end of the dump
+pub(crate) fn message_dump(
+    header: &IppHeader,
+    attributes: &IppAttributes,
+    raw: Option<&[u8]>,
+    payload_offset: usize,
+    response: bool,
+) -> String {
+    if let Some(raw) = raw {
+        return hex_dump(&raw[..payload_offset.min(raw.len())], response);
+    }
+    let mut buf = Vec::new();
+    let result = header.write(&mut buf).and_then(|_| attributes.write(&mut buf));
+    let mut dump = hex_dump(&buf, response);
+    if let Err(e) = result {
+        dump.push_str(&format!("serialization failed after {} bytes: {}\n", buf.len(), e));
+    }
+    dump
+}
+
+/// Produce annotated hex dump with offset, bytes and ASCII columns followed by tag annotations
+fn hex_dump(data: &[u8], response: bool) -> String {
+    let annotations = annotate(data, response);
+    let mut result = String::new();
+
+    for (line, chunk) in data.chunks(BYTES_PER_LINE).enumerate() {
+        let start = line * BYTES_PER_LINE;
+
+        let mut hex = String::new();
+        for (i, byte) in chunk.iter().enumerate() {
+            if i == BYTES_PER_LINE / 2 {
+                hex.push(' ');
+            }
+            hex.push_str(&format!("{:02x} ", byte));
+        }
+        let ascii = chunk
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+
+        result.push_str(&format!("{:08x}  {:<49} |{}|\n", start, hex, ascii));
+
+        for (offset, annotation) in annotations
+            .iter()
+            .filter(|(offset, _)| *offset >= start && *offset < start + chunk.len())
+        {
+            result.push_str(&format!("          <-- {:04x}: {}\n", offset, annotation));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        attribute::IppAttribute,
+        ipp::{DelimiterTag, IppVersion, StatusCode},
+        operation::IppOperation,
+        request::IppRequestResponse,
+        IppOperationBuilder, IppValue,
+    };
+
+    #[test]
+    fn test_hex_dump() {
+        let request = IppOperationBuilder::get_printer_attributes()
+            .build()
+            .into_ipp_request("ipp://localhost");
+        let dump = request.hex_dump(false);
+
+        assert!(dump.starts_with("00000000  01 01 00 0b 00 00 00 01  01 47 "));
+        assert!(dump.contains("<-- 0002: operation: GetPrinterAttributes\n"));
+        assert!(dump.contains("<-- 0008: group: OperationAttributes\n"));
+        assert!(dump.contains("Charset attributes-charset: utf-8\n"));
+        assert!(dump.contains("Uri printer-uri: ipp://localhost\n"));
+        assert!(dump.trim_end().ends_with("end-of-attributes"));
+    }
+
+    #[test]
+    fn test_hex_dump_response() {
+        // status 2 is also the Print-Job operation code
+        let response =
+            IppRequestResponse::new_response(IppVersion::Ipp11, StatusCode::SuccessfulOKConflictingAttributes, 1);
+        let dump = response.hex_dump(true);
+        assert!(dump.contains("<-- 0002: status: SuccessfulOKConflictingAttributes\n"));
+        assert!(!dump.contains("operation:"));
+
+        let mut response = IppRequestResponse::new_response(IppVersion::Ipp11, StatusCode::SuccessfulOK, 1);
+        response.attributes_mut().add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new("printer-info", IppValue::TextWithoutLanguage("x".repeat(70000))),
+        );
+        assert!(response.hex_dump(true).contains("serialization failed after "));
+    }
+
+    #[test]
+    fn test_hex_dump_raw() {
+        // printer group before the operation group, re-serializing would write the operation group first
+        let mut data = vec![0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x04];
+        data.extend_from_slice(b"\x23\x00\x0dprinter-state\x00\x04\x00\x00\x00\x03\x01");
+        data.extend_from_slice(b"\x47\x00\x12attributes-charset\x00\x05utf-8");
+        data.extend_from_slice(b"\x48\x00\x1battributes-natural-language\x00\x02en\x03");
+
+        let response = IppRequestResponse::from_bytes(&data).unwrap();
+        let dump = response.hex_dump(true);
+        assert!(dump.contains("<-- 0008: group: PrinterAttributes\n"));
+        assert!(dump.contains("<-- 0009: Enum printer-state: 3\n"));
+        assert!(dump.trim_end().ends_with("end-of-attributes"));
+    }
+}
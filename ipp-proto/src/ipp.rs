@@ -34,6 +34,15 @@ pub enum Operation {
     PausePrinter = 0x0010,
     ResumePrinter = 0x0011,
     PurgeJobs = 0x0012,
+    CreatePrinterSubscriptions = 0x0016,
+    CreateJobSubscriptions = 0x0017,
+    GetSubscriptionAttributes = 0x0018,
+    GetSubscriptions = 0x0019,
+    RenewSubscription = 0x001A,
+    CancelSubscription = 0x001B,
+    GetNotifications = 0x001C,
+    GetDocuments = 0x0035,
+    IdentifyPrinter = 0x003C,
 
     CupsGetDefault = 0x4001,
     CupsGetPrinters = 0x4002,
@@ -62,6 +71,16 @@ pub enum PrinterState {
     Stopped = 5,
 }
 
+impl fmt::Display for PrinterState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PrinterState::Idle => write!(f, "idle"),
+            PrinterState::Processing => write!(f, "processing"),
+            PrinterState::Stopped => write!(f, "stopped"),
+        }
+    }
+}
+
 /// paper orientation constants
 #[derive(Primitive, Debug, Copy, Clone, PartialEq)]
 pub enum Orientation {
@@ -111,6 +130,12 @@ pub enum DelimiterTag {
     EndOfAttributes = 0x03,
     PrinterAttributes = 0x04,
     UnsupportedAttributes = 0x05,
+    SubscriptionAttributes = 0x06,
+    EventNotificationAttributes = 0x07,
+    DocumentAttributes = 0x09,
+    /// Group started by a delimiter tag which is not recognized, for example a vendor group.
+    /// Never appears on the wire: such groups are only produced by a non-strict parser and are not serialized
+    UnknownAttributes = 0xff,
 }
 
 /// IPP value tags
@@ -147,6 +172,8 @@ pub enum StatusCode {
     SuccessfulOK = 0x0000,
     SuccessfulOKIgnoredOrSubstitutedAttributes = 0x0001,
     SuccessfulOKConflictingAttributes = 0x0002,
+    SuccessfulOKIgnoredSubscriptions = 0x0003,
+    SuccessfulOKIgnoredNotifications = 0x0004,
     ClientErrorBadRequest = 0x0400,
     ClientErrorForbidden = 0x0401,
     ClientErrorNotAuthenticated = 0x0402,
@@ -178,12 +205,22 @@ pub enum StatusCode {
     ServerErrorMultipleDocumentJobsNotSupported = 0x0509,
 }
 
+impl StatusCode {
+    /// Whether the status is in the successful range (0x0000-0x00ff).
+    /// Successful responses may still report ignored or substituted attributes
+    pub fn is_success(&self) -> bool {
+        (*self as u16) <= 0x00ff
+    }
+}
+
 impl fmt::Display for StatusCode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             StatusCode::SuccessfulOK => write!(f, "No error"),
             StatusCode::SuccessfulOKIgnoredOrSubstitutedAttributes => write!(f, "Ignored or substituted attributes"),
             StatusCode::SuccessfulOKConflictingAttributes => write!(f, "Conflicting attributes"),
+            StatusCode::SuccessfulOKIgnoredSubscriptions => write!(f, "Ignored subscriptions"),
+            StatusCode::SuccessfulOKIgnoredNotifications => write!(f, "Ignored notifications"),
             StatusCode::ClientErrorBadRequest => write!(f, "Bad request"),
             StatusCode::ClientErrorForbidden => write!(f, "Forbidden"),
             StatusCode::ClientErrorNotAuthenticated => write!(f, "Not authenticated"),
@@ -218,3 +255,36 @@ impl fmt::Display for StatusCode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use num_traits::FromPrimitive;
+
+    use super::*;
+
+    #[test]
+    fn test_success_status_codes() {
+        let codes = [
+            (0x0000, StatusCode::SuccessfulOK),
+            (0x0001, StatusCode::SuccessfulOKIgnoredOrSubstitutedAttributes),
+            (0x0002, StatusCode::SuccessfulOKConflictingAttributes),
+            (0x0003, StatusCode::SuccessfulOKIgnoredSubscriptions),
+            (0x0004, StatusCode::SuccessfulOKIgnoredNotifications),
+        ];
+        for (code, status) in &codes {
+            assert_eq!(StatusCode::from_u16(*code), Some(*status));
+            assert!(status.is_success());
+        }
+        assert!(!StatusCode::ClientErrorBadRequest.is_success());
+        assert!(!StatusCode::ServerErrorInternalError.is_success());
+    }
+
+    #[test]
+    fn test_printer_state() {
+        assert_eq!(PrinterState::from_i32(3), Some(PrinterState::Idle));
+        assert_eq!(PrinterState::from_i32(4), Some(PrinterState::Processing));
+        assert_eq!(PrinterState::from_i32(5), Some(PrinterState::Stopped));
+        assert_eq!(PrinterState::from_i32(6), None);
+        assert_eq!(PrinterState::Processing.to_string(), "processing");
+    }
+}
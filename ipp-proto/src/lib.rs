@@ -1,38 +1,142 @@
-use std::io::{self, Read, Write};
+use std::{
+    io::{self, Read, Write},
+    mem,
+    path::Path,
+};
 
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use bytes::{Bytes, BytesMut};
-use futures::{try_ready, Async, Poll, Stream};
+use flate2::write::{DeflateEncoder, GzEncoder};
+use futures::{try_ready, Async, Future, Poll, Stream};
 use num_traits::FromPrimitive;
-use tokio::io::AsyncRead;
+use tokio_io::{io::AllowStdIo, AsyncRead};
+
+use crate::model::Compression;
 
 pub use crate::{
     attribute::{IppAttribute, IppAttributeGroup, IppAttributes},
     builder::{
         CreateJobBuilder, GetPrinterAttributesBuilder, IppOperationBuilder, PrintJobBuilder, SendDocumentBuilder,
     },
+    capabilities::PrinterCapabilities,
     ipp::{IppVersion, Operation, StatusCode},
-    parser::{AsyncIppParser, IppParser, ParseError},
+    parser::{AsyncIppParser, AsyncIppStreamParser, IppParseEvent, IppParser, IppPayloadStream, ParseError},
     request::{IppRequestResponse, PayloadKind},
-    value::IppValue,
+    value::{InvalidDateTime, IppValue, TypeMismatch},
 };
 
 pub mod attribute;
 pub mod builder;
+pub mod capabilities;
+mod hexdump;
 pub mod ipp;
+pub mod model;
 pub mod operation;
 pub mod parser;
 pub mod request;
 pub mod value;
 
+// compressor of the job data, output is collected in memory and taken after each chunk
+enum PayloadEncoder {
+    Gzip(GzEncoder<Vec<u8>>),
+    Deflate(DeflateEncoder<Vec<u8>>),
+}
+
+impl PayloadEncoder {
+    fn write(&mut self, data: &[u8]) -> io::Result<Vec<u8>> {
+        let output = match *self {
+            PayloadEncoder::Gzip(ref mut e) => {
+                e.write_all(data)?;
+                e.get_mut()
+            }
+            PayloadEncoder::Deflate(ref mut e) => {
+                e.write_all(data)?;
+                e.get_mut()
+            }
+        };
+        Ok(mem::take(output))
+    }
+
+    fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            PayloadEncoder::Gzip(e) => e.finish(),
+            PayloadEncoder::Deflate(e) => e.finish(),
+        }
+    }
+}
+
 /// Source for IPP data stream (job file)
 pub struct IppJobSource {
     inner: Box<dyn AsyncRead + Send>,
     buffer: Vec<u8>,
+    length: Option<u64>,
+    // boxed, the encoder state is large compared to the rest of the source
+    encoder: Option<Box<PayloadEncoder>>,
 }
 
 impl IppJobSource {
     const CHUNK_SIZE: usize = 32768;
+
+    /// Create job source from AsyncRead which produces exactly `length` bytes.
+    /// Requests with such a source are sent with `Content-Length` instead of chunked encoding
+    pub fn with_length<T>(r: T, length: u64) -> IppJobSource
+    where
+        T: 'static + AsyncRead + Send,
+    {
+        IppJobSource {
+            length: Some(length),
+            ..IppJobSource::from(r)
+        }
+    }
+
+    /// Open file as job source, taking the length from the file metadata
+    pub fn open<P>(path: P) -> impl Future<Item = IppJobSource, Error = io::Error>
+    where
+        P: AsRef<Path> + Send + 'static,
+    {
+        tokio_fs::File::open(path)
+            .and_then(|file| file.metadata())
+            .map(|(file, metadata)| IppJobSource::with_length(file, metadata.len()))
+    }
+
+    /// Create job source from a blocking reader such as `std::fs::File`, for the blocking client
+    pub fn from_std<T>(r: T) -> IppJobSource
+    where
+        T: 'static + Read + Send,
+    {
+        IppJobSource::from(AllowStdIo::new(r))
+    }
+
+    /// Open file as job source without a runtime, taking the length from the file metadata
+    pub fn open_std<P>(path: P) -> io::Result<IppJobSource>
+    where
+        P: AsRef<Path>,
+    {
+        let file = std::fs::File::open(path)?;
+        let length = file.metadata()?.len();
+        Ok(IppJobSource::with_length(AllowStdIo::new(file), length))
+    }
+
+    /// Length of the job data in bytes, if known
+    pub fn length(&self) -> Option<u64> {
+        self.length
+    }
+
+    /// Compress the job data while it is streamed. The compressed length is not known in advance,
+    /// so requests with a compressed source are sent with chunked encoding
+    pub fn compress(mut self, compression: Compression) -> IppJobSource {
+        let level = flate2::Compression::default();
+        self.encoder = match compression {
+            Compression::None => return self,
+            Compression::Gzip => Some(Box::new(PayloadEncoder::Gzip(GzEncoder::new(Vec::new(), level)))),
+            Compression::Deflate => Some(Box::new(PayloadEncoder::Deflate(DeflateEncoder::new(
+                Vec::new(),
+                level,
+            )))),
+        };
+        self.length = None;
+        self
+    }
 }
 
 impl Stream for IppJobSource {
@@ -40,11 +144,23 @@ impl Stream for IppJobSource {
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
-        let size = try_ready!(self.inner.poll_read(&mut self.buffer));
-        if size > 0 {
-            Ok(Async::Ready(Some(self.buffer[0..size].into())))
-        } else {
-            Ok(Async::Ready(None))
+        loop {
+            let size = try_ready!(self.inner.poll_read(&mut self.buffer));
+            match self.encoder {
+                None if size > 0 => return Ok(Async::Ready(Some(self.buffer[0..size].into()))),
+                None => return Ok(Async::Ready(None)),
+                Some(ref mut encoder) if size > 0 => {
+                    // the encoder may buffer small chunks without producing output
+                    let output = encoder.write(&self.buffer[0..size])?;
+                    if !output.is_empty() {
+                        return Ok(Async::Ready(Some(output.into())));
+                    }
+                }
+                Some(_) => {
+                    let output = self.encoder.take().map(|encoder| encoder.finish()).transpose()?;
+                    return Ok(Async::Ready(output.map(Bytes::from)));
+                }
+            }
         }
     }
 }
@@ -58,10 +174,32 @@ where
         IppJobSource {
             inner: Box::new(r),
             buffer: vec![0; IppJobSource::CHUNK_SIZE],
+            length: None,
+            encoder: None,
         }
     }
 }
 
+/// Guess document-format MIME type from the first bytes of the document.
+///
+/// Recognizes PDF, PostScript, JPEG, PNG, PWG raster and Apple raster (URF).
+/// At most the first 8 bytes are examined.
+pub fn guess_document_format(data: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"%PDF", "application/pdf"),
+        (b"%!", "application/postscript"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"RaS2", "image/pwg-raster"),
+        (b"UNIRAST\0", "image/urf"),
+    ];
+
+    SIGNATURES
+        .iter()
+        .find(|(signature, _)| data.starts_with(signature))
+        .map(|(_, format)| *format)
+}
+
 pub(crate) trait IppWriter {
     fn write(&self, writer: &mut dyn Write) -> io::Result<usize>;
 }
@@ -97,7 +235,7 @@ impl IppHeader {
     /// Create IppHeader from the reader
     pub fn from_reader(reader: &mut dyn Read) -> Result<IppHeader, ParseError> {
         let retval = IppHeader::new(
-            IppVersion::from_u16(reader.read_u16::<BigEndian>()?).ok_or_else(|| ParseError::InvalidVersion)?,
+            IppVersion::from_u16(reader.read_u16::<BigEndian>()?).ok_or(ParseError::InvalidVersion)?,
             reader.read_u16::<BigEndian>()?,
             reader.read_u32::<BigEndian>()?,
         );
@@ -155,10 +293,7 @@ mod tests {
 
         let header = IppHeader::from_reader(&mut Cursor::new(data));
         assert!(header.is_err());
-        if let Some(ParseError::InvalidVersion) = header.err() {
-        } else {
-            assert!(false);
-        }
+        assert!(matches!(header.err(), Some(ParseError::InvalidVersion)));
     }
 
     #[test]
@@ -168,4 +303,64 @@ mod tests {
         assert!(header.write(&mut Cursor::new(&mut buf)).is_ok());
         assert_eq!(buf, vec![0x02, 0x01, 0x12, 0x34, 0xaa, 0x55, 0xaa, 0x55]);
     }
+
+    #[test]
+    fn test_version_bytes() {
+        for &(version, bytes) in &[
+            (IppVersion::Ipp10, [1, 0]),
+            (IppVersion::Ipp11, [1, 1]),
+            (IppVersion::Ipp20, [2, 0]),
+            (IppVersion::Ipp21, [2, 1]),
+            (IppVersion::Ipp22, [2, 2]),
+        ] {
+            let mut buf = Vec::new();
+            IppHeader::new(version, 0, 1).write(&mut buf).unwrap();
+            assert_eq!(buf[..2], bytes);
+            assert_eq!(IppHeader::from_reader(&mut Cursor::new(buf)).unwrap().version, version);
+        }
+    }
+
+    #[test]
+    fn test_content_length() {
+        use crate::operation::IppOperation;
+
+        let request = IppOperationBuilder::get_printer_attributes()
+            .build()
+            .into_ipp_request("ipp://localhost");
+        let mut buf = Vec::new();
+        let size = request.header().write(&mut buf).unwrap() + request.attributes().write(&mut buf).unwrap();
+        assert_eq!(request.content_length(), Some(size as u64));
+
+        let data = vec![0u8; 100];
+        let sized = IppJobSource::with_length(Cursor::new(data.clone()), 100);
+        let request = IppOperationBuilder::print_job(sized)
+            .build()
+            .into_ipp_request("ipp://localhost");
+        let mut buf = Vec::new();
+        let size = request.header().write(&mut buf).unwrap() + request.attributes().write(&mut buf).unwrap();
+        assert_eq!(request.content_length(), Some(size as u64 + 100));
+
+        let request = IppOperationBuilder::print_job(Cursor::new(data))
+            .build()
+            .into_ipp_request("ipp://localhost");
+        assert_eq!(request.content_length(), None);
+    }
+
+    #[test]
+    fn test_guess_document_format() {
+        assert_eq!(guess_document_format(b"%PDF-1.7\n"), Some("application/pdf"));
+        assert_eq!(guess_document_format(b"%!PS-Adobe-3.0"), Some("application/postscript"));
+        assert_eq!(
+            guess_document_format(&[0xff, 0xd8, 0xff, 0xe0, 0, 0x10, b'J', b'F']),
+            Some("image/jpeg")
+        );
+        assert_eq!(
+            guess_document_format(&[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']),
+            Some("image/png")
+        );
+        assert_eq!(guess_document_format(b"RaS2PwgRaster\0"), Some("image/pwg-raster"));
+        assert_eq!(guess_document_format(b"UNIRAST\0\0\0\0\x01"), Some("image/urf"));
+        assert_eq!(guess_document_format(b"hello"), None);
+        assert_eq!(guess_document_format(b"%P"), None);
+    }
 }
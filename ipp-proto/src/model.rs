@@ -0,0 +1,1086 @@
+//!
+//! Typed models for structured IPP attribute values
+//!
+use std::collections::HashMap;
+
+use num_traits::FromPrimitive;
+
+use crate::{
+    attribute::*,
+    capabilities::PrinterCapabilities,
+    ipp::{DelimiterTag, JobState, PrinterState},
+    IppAttribute, IppAttributeGroup, IppAttributes, IppValue,
+};
+
+// iterate over (member name, first member value) pairs of a flattened collection
+fn collection_members(value: &IppValue) -> Vec<(&str, &IppValue)> {
+    let mut result = Vec::new();
+    if let IppValue::Collection(ref list) = *value {
+        let mut iter = list.iter().peekable();
+        while let Some(item) = iter.next() {
+            if let IppValue::MemberAttrName(ref name) = *item {
+                if let Some(member) = iter.peek() {
+                    if member.as_memberattrname().is_none() {
+                        result.push((name.as_str(), *member));
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+// iterate over (lowercase key, value) pairs of a `key=value;key=value` string
+fn key_values(s: &str) -> impl Iterator<Item = (String, &str)> {
+    s.split(&[';', ','][..]).filter_map(|field| {
+        let mut kv = field.splitn(2, '=');
+        let key = kv.next()?.trim().to_ascii_lowercase();
+        let value = kv.next()?.trim();
+        Some((key, value))
+    })
+}
+
+/// Media dimensions in hundredths of millimeters
+#[derive(Clone, Debug, PartialEq)]
+pub struct MediaSize {
+    pub x_dimension: i32,
+    pub y_dimension: i32,
+}
+
+impl MediaSize {
+    /// Create media size from dimensions in hundredths of millimeters
+    pub fn new(x_dimension: i32, y_dimension: i32) -> MediaSize {
+        MediaSize {
+            x_dimension,
+            y_dimension,
+        }
+    }
+
+    /// Parse dimensions from a PWG 5101.1 self-describing media name, for example `iso_a4_210x297mm`
+    pub fn from_pwg_name(name: &str) -> Option<MediaSize> {
+        let dimensions = name.rsplit('_').next()?;
+        let (dimensions, scale) = if let Some(dims) = dimensions.strip_suffix("mm") {
+            (dims, 100.0)
+        } else if let Some(dims) = dimensions.strip_suffix("in") {
+            (dims, 2540.0)
+        } else {
+            return None;
+        };
+
+        let mut parts = dimensions.split('x');
+        let x = parts.next()?.parse::<f64>().ok()?;
+        let y = parts.next()?.parse::<f64>().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(MediaSize::new((x * scale).round() as i32, (y * scale).round() as i32))
+    }
+
+    /// Convert to media-size collection value
+    pub fn to_value(&self) -> IppValue {
+        IppValue::collection(vec![
+            (X_DIMENSION, IppValue::Integer(self.x_dimension)),
+            (Y_DIMENSION, IppValue::Integer(self.y_dimension)),
+        ])
+    }
+
+    /// Create media size from media-size collection value
+    pub fn from_value(value: &IppValue) -> Option<MediaSize> {
+        let members = collection_members(value);
+        let get = |name: &str| {
+            members
+                .iter()
+                .find(|m| m.0 == name)
+                .and_then(|m| m.1.as_integer())
+                .cloned()
+        };
+
+        Some(MediaSize::new(get(X_DIMENSION)?, get(Y_DIMENSION)?))
+    }
+}
+
+/// Typed media-col collection
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MediaCol {
+    pub media_size_name: Option<String>,
+    pub media_size: Option<MediaSize>,
+    pub media_type: Option<String>,
+    pub media_source: Option<String>,
+    /// Margins in hundredths of millimeters
+    pub media_bottom_margin: Option<i32>,
+    pub media_left_margin: Option<i32>,
+    pub media_right_margin: Option<i32>,
+    pub media_top_margin: Option<i32>,
+}
+
+impl MediaCol {
+    /// Create empty media-col
+    pub fn new() -> MediaCol {
+        MediaCol::default()
+    }
+
+    /// Create media-col from a PWG media name such as one reported in `media-ready`
+    pub fn from_media_name(name: &str) -> MediaCol {
+        MediaCol {
+            media_size_name: Some(name.to_owned()),
+            media_size: MediaSize::from_pwg_name(name),
+            ..Default::default()
+        }
+    }
+
+    /// Convert to media-col collection value
+    pub fn to_value(&self) -> IppValue {
+        let mut members = Vec::new();
+
+        if let Some(ref size) = self.media_size {
+            members.push((MEDIA_SIZE, size.to_value()));
+        }
+        if let Some(ref name) = self.media_size_name {
+            members.push((MEDIA_SIZE_NAME, IppValue::Keyword(name.clone())));
+        }
+        if let Some(ref media_type) = self.media_type {
+            members.push((MEDIA_TYPE, IppValue::Keyword(media_type.clone())));
+        }
+        if let Some(ref source) = self.media_source {
+            members.push((MEDIA_SOURCE, IppValue::Keyword(source.clone())));
+        }
+        for (name, margin) in &[
+            (MEDIA_BOTTOM_MARGIN, self.media_bottom_margin),
+            (MEDIA_LEFT_MARGIN, self.media_left_margin),
+            (MEDIA_RIGHT_MARGIN, self.media_right_margin),
+            (MEDIA_TOP_MARGIN, self.media_top_margin),
+        ] {
+            if let Some(margin) = margin {
+                members.push((name, IppValue::Integer(*margin)));
+            }
+        }
+
+        IppValue::collection(members)
+    }
+
+    /// Create media-col from media-col collection value. Returns None if value is not a collection
+    pub fn from_value(value: &IppValue) -> Option<MediaCol> {
+        value.as_collection()?;
+
+        let mut media_col = MediaCol::new();
+        for (name, member) in collection_members(value) {
+            match name {
+                MEDIA_SIZE => media_col.media_size = MediaSize::from_value(member),
+                MEDIA_SIZE_NAME => media_col.media_size_name = Some(member.to_string()),
+                MEDIA_TYPE => media_col.media_type = Some(member.to_string()),
+                MEDIA_SOURCE => media_col.media_source = Some(member.to_string()),
+                MEDIA_BOTTOM_MARGIN => media_col.media_bottom_margin = member.as_integer().cloned(),
+                MEDIA_LEFT_MARGIN => media_col.media_left_margin = member.as_integer().cloned(),
+                MEDIA_RIGHT_MARGIN => media_col.media_right_margin = member.as_integer().cloned(),
+                MEDIA_TOP_MARGIN => media_col.media_top_margin = member.as_integer().cloned(),
+                _ => {}
+            }
+        }
+        Some(media_col)
+    }
+
+    /// Whether all four margins are reported as zero, so the media can be printed full-bleed
+    pub fn is_borderless(&self) -> bool {
+        [
+            self.media_bottom_margin,
+            self.media_left_margin,
+            self.media_right_margin,
+            self.media_top_margin,
+        ]
+        .iter()
+        .all(|margin| *margin == Some(0))
+    }
+}
+
+/// Type of separator sheets, member of `separator-sheets`
+#[derive(Clone, Debug, PartialEq)]
+pub enum SeparatorSheetsType {
+    None,
+    SlipSheets,
+    StartSheet,
+    EndSheet,
+    BothSheets,
+    Other(String),
+}
+
+impl SeparatorSheetsType {
+    /// Create separator sheets type from keyword
+    pub fn from_keyword(keyword: &str) -> SeparatorSheetsType {
+        match keyword {
+            "none" => SeparatorSheetsType::None,
+            "slip-sheets" => SeparatorSheetsType::SlipSheets,
+            "start-sheet" => SeparatorSheetsType::StartSheet,
+            "end-sheet" => SeparatorSheetsType::EndSheet,
+            "both-sheets" => SeparatorSheetsType::BothSheets,
+            other => SeparatorSheetsType::Other(other.to_owned()),
+        }
+    }
+
+    /// IPP keyword of the separator sheets type
+    pub fn as_keyword(&self) -> &str {
+        match *self {
+            SeparatorSheetsType::None => "none",
+            SeparatorSheetsType::SlipSheets => "slip-sheets",
+            SeparatorSheetsType::StartSheet => "start-sheet",
+            SeparatorSheetsType::EndSheet => "end-sheet",
+            SeparatorSheetsType::BothSheets => "both-sheets",
+            SeparatorSheetsType::Other(ref keyword) => keyword,
+        }
+    }
+}
+
+/// Typed separator-sheets collection
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeparatorSheets {
+    pub separator_sheets_type: SeparatorSheetsType,
+    pub media: Option<String>,
+}
+
+impl SeparatorSheets {
+    /// Create separator-sheets of a given type using the printer default media
+    pub fn new(separator_sheets_type: SeparatorSheetsType) -> SeparatorSheets {
+        SeparatorSheets {
+            separator_sheets_type,
+            media: None,
+        }
+    }
+
+    /// Print separator sheets on the given media keyword, for example `na_letter_8.5x11in`
+    pub fn media(mut self, media: &str) -> Self {
+        self.media = Some(media.to_owned());
+        self
+    }
+
+    /// Convert to separator-sheets collection value
+    pub fn to_value(&self) -> IppValue {
+        let mut members = vec![(
+            SEPARATOR_SHEETS_TYPE,
+            IppValue::Keyword(self.separator_sheets_type.as_keyword().to_owned()),
+        )];
+        if let Some(ref media) = self.media {
+            members.push((MEDIA, IppValue::Keyword(media.clone())));
+        }
+        IppValue::collection(members)
+    }
+
+    /// Create separator-sheets from collection value. Returns None if value is not a collection
+    /// or has no separator-sheets-type member
+    pub fn from_value(value: &IppValue) -> Option<SeparatorSheets> {
+        let members = collection_members(value);
+        let separator_sheets_type = members
+            .iter()
+            .find(|(name, _)| *name == SEPARATOR_SHEETS_TYPE)
+            .map(|(_, value)| SeparatorSheetsType::from_keyword(&value.to_string()))?;
+        let media = members
+            .iter()
+            .find(|(name, _)| *name == MEDIA)
+            .map(|(_, value)| value.to_string());
+        Some(SeparatorSheets {
+            separator_sheets_type,
+            media,
+        })
+    }
+
+    /// Check the referenced media against `media-supported` of the printer.
+    ///
+    /// Returns None if no media is referenced or the printer does not report supported media.
+    pub fn is_supported_by(&self, capabilities: &PrinterCapabilities) -> Option<bool> {
+        let media = self.media.as_ref()?;
+        capabilities.supports(&IppAttribute::new(MEDIA, IppValue::Keyword(media.clone())))
+    }
+}
+
+/// Collation of multiple copies of a multi-document job, from `multiple-document-handling`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MultipleDocumentHandling {
+    /// All documents are treated as a single document, copies are collated
+    SingleDocument,
+    /// Each document is printed separately, copies are collated
+    SeparateDocumentsCollatedCopies,
+    /// Each document is printed separately, copies of each document are stacked together
+    SeparateDocumentsUncollatedCopies,
+    /// Like `SingleDocument`, but each document starts on a new sheet
+    SingleDocumentNewSheet,
+}
+
+impl MultipleDocumentHandling {
+    /// Create multiple document handling from keyword. Returns None for unknown keywords
+    pub fn from_keyword(keyword: &str) -> Option<MultipleDocumentHandling> {
+        match keyword {
+            "single-document" => Some(MultipleDocumentHandling::SingleDocument),
+            "separate-documents-collated-copies" => Some(MultipleDocumentHandling::SeparateDocumentsCollatedCopies),
+            "separate-documents-uncollated-copies" => Some(MultipleDocumentHandling::SeparateDocumentsUncollatedCopies),
+            "single-document-new-sheet" => Some(MultipleDocumentHandling::SingleDocumentNewSheet),
+            _ => None,
+        }
+    }
+
+    /// IPP keyword of the multiple document handling
+    pub fn as_keyword(&self) -> &'static str {
+        match *self {
+            MultipleDocumentHandling::SingleDocument => "single-document",
+            MultipleDocumentHandling::SeparateDocumentsCollatedCopies => "separate-documents-collated-copies",
+            MultipleDocumentHandling::SeparateDocumentsUncollatedCopies => "separate-documents-uncollated-copies",
+            MultipleDocumentHandling::SingleDocumentNewSheet => "single-document-new-sheet",
+        }
+    }
+}
+
+/// Duplex mode, from `sides`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sides {
+    OneSided,
+    /// Pages are flipped along the long edge, for portrait documents
+    TwoSidedLongEdge,
+    /// Pages are flipped along the short edge, for landscape documents
+    TwoSidedShortEdge,
+}
+
+impl Sides {
+    /// Create sides from keyword. Returns None for unknown keywords
+    pub fn from_keyword(keyword: &str) -> Option<Sides> {
+        match keyword {
+            "one-sided" => Some(Sides::OneSided),
+            "two-sided-long-edge" => Some(Sides::TwoSidedLongEdge),
+            "two-sided-short-edge" => Some(Sides::TwoSidedShortEdge),
+            _ => None,
+        }
+    }
+
+    /// IPP keyword of the sides
+    pub fn as_keyword(&self) -> &'static str {
+        match *self {
+            Sides::OneSided => "one-sided",
+            Sides::TwoSidedLongEdge => "two-sided-long-edge",
+            Sides::TwoSidedShortEdge => "two-sided-short-edge",
+        }
+    }
+}
+
+/// Document compression, from `compression`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Compression {
+    None,
+    /// gzip format, RFC 1952
+    Gzip,
+    /// Raw deflate format, RFC 1951
+    Deflate,
+}
+
+impl Compression {
+    /// Create compression from keyword. Returns None for unknown keywords
+    pub fn from_keyword(keyword: &str) -> Option<Compression> {
+        match keyword {
+            "none" => Some(Compression::None),
+            "gzip" => Some(Compression::Gzip),
+            "deflate" => Some(Compression::Deflate),
+            _ => None,
+        }
+    }
+
+    /// IPP keyword of the compression
+    pub fn as_keyword(&self) -> &'static str {
+        match *self {
+            Compression::None => "none",
+            Compression::Gzip => "gzip",
+            Compression::Deflate => "deflate",
+        }
+    }
+}
+
+/// Rendering optimization hint, from `print-content-optimize`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ContentOptimize {
+    Auto,
+    Text,
+    Graphic,
+    Photo,
+    TextAndGraphic,
+}
+
+impl ContentOptimize {
+    /// Create content optimization from keyword. Returns None for unknown keywords
+    pub fn from_keyword(keyword: &str) -> Option<ContentOptimize> {
+        match keyword {
+            "auto" => Some(ContentOptimize::Auto),
+            "text" => Some(ContentOptimize::Text),
+            "graphic" => Some(ContentOptimize::Graphic),
+            "photo" => Some(ContentOptimize::Photo),
+            "text-and-graphic" => Some(ContentOptimize::TextAndGraphic),
+            _ => None,
+        }
+    }
+
+    /// IPP keyword of the content optimization
+    pub fn as_keyword(&self) -> &'static str {
+        match *self {
+            ContentOptimize::Auto => "auto",
+            ContentOptimize::Text => "text",
+            ContentOptimize::Graphic => "graphic",
+            ContentOptimize::Photo => "photo",
+            ContentOptimize::TextAndGraphic => "text-and-graphic",
+        }
+    }
+}
+
+/// IEEE 1284 device ID as reported in `printer-device-id`, for example `MFG:HP;MDL:LaserJet 4;CMD:PCL,PS;`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeviceId {
+    fields: Vec<(String, String)>,
+}
+
+impl DeviceId {
+    /// Parse device ID string into key-value fields. Keys are matched case-insensitively
+    pub fn parse(device_id: &str) -> DeviceId {
+        let fields = device_id
+            .split(';')
+            .filter_map(|field| {
+                let mut kv = field.splitn(2, ':');
+                let key = kv.next()?.trim();
+                let value = kv.next()?.trim();
+                if key.is_empty() {
+                    None
+                } else {
+                    Some((key.to_owned(), value.to_owned()))
+                }
+            })
+            .collect();
+        DeviceId { fields }
+    }
+
+    /// Get all fields in the order of appearance
+    pub fn fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+
+    /// Get field value by any of the given keys
+    pub fn get(&self, keys: &[&str]) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| keys.iter().any(|key| k.eq_ignore_ascii_case(key)))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Manufacturer (MFG or MANUFACTURER field)
+    pub fn manufacturer(&self) -> Option<&str> {
+        self.get(&["MFG", "MANUFACTURER"])
+    }
+
+    /// Model (MDL or MODEL field)
+    pub fn model(&self) -> Option<&str> {
+        self.get(&["MDL", "MODEL"])
+    }
+
+    /// Supported command sets (CMD or COMMAND SET field)
+    pub fn command_set(&self) -> Vec<&str> {
+        self.get(&["CMD", "COMMAND SET"])
+            .map(|v| v.split(',').map(str::trim).filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Description (DES or DESCRIPTION field)
+    pub fn description(&self) -> Option<&str> {
+        self.get(&["DES", "DESCRIPTION"])
+    }
+
+    /// Serial number (SN, SERN or SERIALNUMBER field)
+    pub fn serial_number(&self) -> Option<&str> {
+        self.get(&["SN", "SERN", "SERIALNUMBER"])
+    }
+}
+
+/// Printer supply as reported in `printer-supply`,
+/// for example `index=1;class=supplyThatIsConsumed;type=toner;unit=percent;maxcapacity=100;level=45;colorantname=black;`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Supply {
+    pub index: Option<i32>,
+    pub class: Option<String>,
+    pub supply_type: Option<String>,
+    pub unit: Option<String>,
+    pub max_capacity: Option<i32>,
+    pub level: Option<i32>,
+    pub colorant_name: Option<String>,
+    /// Human-readable description from the corresponding `printer-supply-description` value
+    pub description: Option<String>,
+}
+
+impl Supply {
+    /// Parse supply from the structured key=value string. Unknown keys are ignored
+    pub fn parse(supply: &str) -> Supply {
+        let mut result = Supply::default();
+
+        for (key, value) in key_values(supply) {
+            match key.as_str() {
+                "index" => result.index = value.parse().ok(),
+                "class" => result.class = Some(value.to_owned()),
+                "type" => result.supply_type = Some(value.to_owned()),
+                "unit" => result.unit = Some(value.to_owned()),
+                "maxcapacity" => result.max_capacity = value.parse().ok(),
+                "level" => result.level = value.parse().ok(),
+                "colorantname" => result.colorant_name = Some(value.to_owned()),
+                _ => {}
+            }
+        }
+        result
+    }
+
+    /// Remaining level in percent of maximum capacity, if both are known and non-negative.
+    /// Negative values have special meaning in RFC 3805 (other, unknown, some remaining)
+    pub fn level_percent(&self) -> Option<i32> {
+        match (self.level, self.max_capacity) {
+            (Some(level), Some(max)) if level >= 0 && max > 0 => Some(level * 100 / max),
+            _ => None,
+        }
+    }
+}
+
+/// Printer alert as reported in `printer-alert`,
+/// for example `code=mediaJam;index=3;severity=critical;group=mediaPath;groupindex=1;time=3682;`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PrinterAlert {
+    pub index: Option<i32>,
+    pub code: Option<String>,
+    pub severity: Option<String>,
+    pub group: Option<String>,
+    pub group_index: Option<i32>,
+    pub location: Option<i32>,
+    /// Value of printer-up-time when the alert was raised
+    pub time: Option<i32>,
+    /// Human-readable description from the corresponding `printer-alert-description` value
+    pub description: Option<String>,
+}
+
+impl PrinterAlert {
+    /// Parse alert from the structured key=value string. Unknown keys are ignored
+    pub fn parse(alert: &str) -> PrinterAlert {
+        let mut result = PrinterAlert::default();
+
+        for (key, value) in key_values(alert) {
+            match key.as_str() {
+                "index" => result.index = value.parse().ok(),
+                "code" => result.code = Some(value.to_owned()),
+                "severity" => result.severity = Some(value.to_owned()),
+                "group" => result.group = Some(value.to_owned()),
+                "groupindex" => result.group_index = value.parse().ok(),
+                "location" => result.location = value.parse().ok(),
+                "time" => result.time = value.parse().ok(),
+                _ => {}
+            }
+        }
+        result
+    }
+}
+
+/// Severity of a printer-state-reasons keyword, given by its suffix
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReasonSeverity {
+    /// `-error` suffix or no suffix: the printer cannot print
+    Error,
+    /// `-warning` suffix: the printer can still print
+    Warning,
+    /// `-report` suffix: informational only
+    Report,
+}
+
+/// Parsed printer-state-reasons keyword, for example `toner-low-warning`
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrinterStateReason {
+    /// Reason keyword without the severity suffix
+    pub reason: String,
+    pub severity: ReasonSeverity,
+}
+
+impl PrinterStateReason {
+    /// Parse reason keyword. Keywords without severity suffix are treated as errors
+    pub fn parse(keyword: &str) -> PrinterStateReason {
+        let suffixes = [
+            ("-error", ReasonSeverity::Error),
+            ("-warning", ReasonSeverity::Warning),
+            ("-report", ReasonSeverity::Report),
+        ];
+        for (suffix, severity) in suffixes.iter() {
+            if let Some(reason) = keyword.strip_suffix(suffix) {
+                return PrinterStateReason {
+                    reason: reason.to_owned(),
+                    severity: *severity,
+                };
+            }
+        }
+        PrinterStateReason {
+            reason: keyword.to_owned(),
+            severity: ReasonSeverity::Error,
+        }
+    }
+}
+
+/// Authentication method from `uri-authentication-supported`
+#[derive(Clone, Debug, PartialEq)]
+pub enum AuthMethod {
+    None,
+    RequestingUserName,
+    Basic,
+    Digest,
+    Certificate,
+    Negotiate,
+    OAuth,
+    Other(String),
+}
+
+impl AuthMethod {
+    /// Create authentication method from keyword
+    pub fn from_keyword(keyword: &str) -> AuthMethod {
+        match keyword {
+            "none" => AuthMethod::None,
+            "requesting-user-name" => AuthMethod::RequestingUserName,
+            "basic" => AuthMethod::Basic,
+            "digest" => AuthMethod::Digest,
+            "certificate" => AuthMethod::Certificate,
+            "negotiate" => AuthMethod::Negotiate,
+            "oauth" => AuthMethod::OAuth,
+            other => AuthMethod::Other(other.to_owned()),
+        }
+    }
+
+    /// Whether the method requires user credentials
+    pub fn requires_credentials(&self) -> bool {
+        !matches!(
+            *self,
+            AuthMethod::None | AuthMethod::RequestingUserName | AuthMethod::Certificate
+        )
+    }
+}
+
+/// Kind of output produced by the printer, from `printer-kind`
+#[derive(Clone, Debug, PartialEq)]
+pub enum PrinterKind {
+    Disc,
+    Document,
+    Envelope,
+    Label,
+    LargeFormat,
+    Photo,
+    Postcard,
+    Receipt,
+    Roll,
+    Other(String),
+}
+
+impl PrinterKind {
+    /// Create printer kind from keyword
+    pub fn from_keyword(keyword: &str) -> PrinterKind {
+        match keyword {
+            "disc" => PrinterKind::Disc,
+            "document" => PrinterKind::Document,
+            "envelope" => PrinterKind::Envelope,
+            "label" => PrinterKind::Label,
+            "large-format" => PrinterKind::LargeFormat,
+            "photo" => PrinterKind::Photo,
+            "postcard" => PrinterKind::Postcard,
+            "receipt" => PrinterKind::Receipt,
+            "roll" => PrinterKind::Roll,
+            other => PrinterKind::Other(other.to_owned()),
+        }
+    }
+}
+
+/// Attributes of a single document object, as returned by Get-Documents
+#[derive(Clone, Debug, Default)]
+pub struct DocumentAttributes {
+    pub document_number: Option<i32>,
+    pub document_name: Option<String>,
+    pub document_format: Option<String>,
+    pub document_state: Option<i32>,
+    /// All attributes of the document
+    pub attributes: HashMap<String, IppAttribute>,
+}
+
+impl DocumentAttributes {
+    /// Create document attributes from a document attributes group
+    pub fn from_group(group: &IppAttributeGroup) -> DocumentAttributes {
+        let attributes = group.attributes();
+        let text = |name: &str| attributes.get(name).map(|attr| attr.value().to_string());
+        let integer = |name: &str| {
+            attributes.get(name).and_then(|attr| match *attr.value() {
+                IppValue::Integer(i) | IppValue::Enum(i) => Some(i),
+                _ => None,
+            })
+        };
+
+        DocumentAttributes {
+            document_number: integer(DOCUMENT_NUMBER),
+            document_name: text(DOCUMENT_NAME),
+            document_format: text(DOCUMENT_FORMAT),
+            document_state: integer(DOCUMENT_STATE),
+            attributes: attributes.clone(),
+        }
+    }
+
+    /// Get all documents from document attributes groups of the response, in the order of appearance
+    pub fn from_attributes(attributes: &IppAttributes) -> Vec<DocumentAttributes> {
+        attributes
+            .groups_of(DelimiterTag::DocumentAttributes)
+            .into_iter()
+            .map(DocumentAttributes::from_group)
+            .collect()
+    }
+}
+
+// string values of a possibly multi-valued attribute
+fn strings<'a>(group: &'a IppAttributeGroup, name: &str) -> Vec<&'a str> {
+    group
+        .attributes()
+        .get(name)
+        .map(|attr| attr.value().as_string_list())
+        .unwrap_or_default()
+}
+
+fn integer(group: &IppAttributeGroup, name: &str) -> Option<i32> {
+    group.attributes().get(name).and_then(|attr| match *attr.value() {
+        IppValue::Integer(i) | IppValue::Enum(i) => Some(i),
+        _ => None,
+    })
+}
+
+/// Printer attributes group with getters for common attributes, borrowed from `IppAttributes::printer`
+#[derive(Clone, Copy, Debug)]
+pub struct PrinterAttributes<'a>(&'a IppAttributeGroup);
+
+impl<'a> PrinterAttributes<'a> {
+    /// Wrap printer attributes group
+    pub fn new(group: &'a IppAttributeGroup) -> PrinterAttributes<'a> {
+        PrinterAttributes(group)
+    }
+
+    /// Underlying attributes group
+    pub fn group(&self) -> &'a IppAttributeGroup {
+        self.0
+    }
+
+    /// Printer name (printer-name)
+    pub fn name(&self) -> Option<&'a str> {
+        strings(self.0, PRINTER_NAME).first().copied()
+    }
+
+    /// Printer state (printer-state), None if absent or not a known enum value
+    pub fn state(&self) -> Option<PrinterState> {
+        integer(self.0, PRINTER_STATE).and_then(PrinterState::from_i32)
+    }
+
+    /// Printer state reason keywords (printer-state-reasons)
+    pub fn state_reasons(&self) -> Vec<&'a str> {
+        strings(self.0, PRINTER_STATE_REASONS)
+    }
+
+    /// Printer URIs (printer-uri-supported)
+    pub fn uri_supported(&self) -> Vec<&'a str> {
+        strings(self.0, PRINTER_URI_SUPPORTED)
+    }
+
+    /// Supported document formats (document-format-supported)
+    pub fn document_formats(&self) -> Vec<&'a str> {
+        strings(self.0, DOCUMENT_FORMAT_SUPPORTED)
+    }
+
+    /// Whether the printer accepts new jobs (printer-is-accepting-jobs)
+    pub fn is_accepting_jobs(&self) -> Option<bool> {
+        self.0
+            .attributes()
+            .get(PRINTER_IS_ACCEPTING_JOBS)
+            .and_then(|attr| attr.value().as_boolean())
+            .cloned()
+    }
+}
+
+/// Job attributes group with getters for common attributes, borrowed from `IppAttributes::jobs`
+#[derive(Clone, Copy, Debug)]
+pub struct JobAttributes<'a>(&'a IppAttributeGroup);
+
+impl<'a> JobAttributes<'a> {
+    /// Wrap job attributes group
+    pub fn new(group: &'a IppAttributeGroup) -> JobAttributes<'a> {
+        JobAttributes(group)
+    }
+
+    /// Underlying attributes group
+    pub fn group(&self) -> &'a IppAttributeGroup {
+        self.0
+    }
+
+    /// Job ID (job-id)
+    pub fn id(&self) -> Option<i32> {
+        integer(self.0, JOB_ID)
+    }
+
+    /// Job name (job-name)
+    pub fn name(&self) -> Option<&'a str> {
+        strings(self.0, JOB_NAME).first().copied()
+    }
+
+    /// Job URI (job-uri)
+    pub fn uri(&self) -> Option<&'a str> {
+        strings(self.0, JOB_URI).first().copied()
+    }
+
+    /// Job state (job-state), None if absent or not a known enum value
+    pub fn state(&self) -> Option<JobState> {
+        integer(self.0, JOB_STATE).and_then(JobState::from_i32)
+    }
+
+    /// Job state reason keywords (job-state-reasons)
+    pub fn state_reasons(&self) -> Vec<&'a str> {
+        strings(self.0, JOB_STATE_REASONS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_size_from_pwg_name() {
+        assert_eq!(
+            MediaSize::from_pwg_name("iso_a4_210x297mm"),
+            Some(MediaSize::new(21000, 29700))
+        );
+        assert_eq!(
+            MediaSize::from_pwg_name("na_letter_8.5x11in"),
+            Some(MediaSize::new(21590, 27940))
+        );
+        assert_eq!(MediaSize::from_pwg_name("auto"), None);
+        assert_eq!(MediaSize::from_pwg_name("iso_a4_210x297x1mm"), None);
+    }
+
+    #[test]
+    fn test_media_col_value() {
+        let mut media_col = MediaCol::from_media_name("iso_a4_210x297mm");
+        media_col.media_source = Some("main".to_owned());
+
+        let value = media_col.to_value();
+        assert_eq!(
+            value,
+            IppValue::Collection(vec![
+                IppValue::MemberAttrName("media-size".to_owned()),
+                IppValue::Collection(vec![
+                    IppValue::MemberAttrName("x-dimension".to_owned()),
+                    IppValue::Integer(21000),
+                    IppValue::MemberAttrName("y-dimension".to_owned()),
+                    IppValue::Integer(29700),
+                ]),
+                IppValue::MemberAttrName("media-size-name".to_owned()),
+                IppValue::Keyword("iso_a4_210x297mm".to_owned()),
+                IppValue::MemberAttrName("media-source".to_owned()),
+                IppValue::Keyword("main".to_owned()),
+            ])
+        );
+        assert_eq!(MediaCol::from_value(&value), Some(media_col));
+        assert_eq!(MediaCol::from_value(&IppValue::Integer(1)), None);
+    }
+
+    #[test]
+    fn test_device_id() {
+        let device_id = DeviceId::parse("MFG:HP;MDL:LaserJet 4;CMD:PCL, PS,;CLS:PRINTER;sn:CN12345;");
+        assert_eq!(device_id.manufacturer(), Some("HP"));
+        assert_eq!(device_id.model(), Some("LaserJet 4"));
+        assert_eq!(device_id.command_set(), vec!["PCL", "PS"]);
+        assert_eq!(device_id.serial_number(), Some("CN12345"));
+        assert_eq!(device_id.description(), None);
+        assert_eq!(device_id.fields().len(), 5);
+    }
+
+    #[test]
+    fn test_supply() {
+        let supply = Supply::parse(
+            "index=1;class=supplyThatIsConsumed;type=toner;unit=percent;maxcapacity=200;level=50;colorantname=black;",
+        );
+        assert_eq!(supply.index, Some(1));
+        assert_eq!(supply.class.as_deref(), Some("supplyThatIsConsumed"));
+        assert_eq!(supply.supply_type.as_deref(), Some("toner"));
+        assert_eq!(supply.unit.as_deref(), Some("percent"));
+        assert_eq!(supply.colorant_name.as_deref(), Some("black"));
+        assert_eq!(supply.level_percent(), Some(25));
+
+        let supply = Supply::parse("type=wasteToner,level=-3,maxcapacity=100");
+        assert_eq!(supply.supply_type.as_deref(), Some("wasteToner"));
+        assert_eq!(supply.level, Some(-3));
+        assert_eq!(supply.level_percent(), None);
+    }
+
+    #[test]
+    fn test_printer_alert() {
+        let alert =
+            PrinterAlert::parse("code=mediaJam;index=3;severity=critical;group=mediaPath;groupindex=1;time=3682;");
+        assert_eq!(alert.code.as_deref(), Some("mediaJam"));
+        assert_eq!(alert.index, Some(3));
+        assert_eq!(alert.severity.as_deref(), Some("critical"));
+        assert_eq!(alert.group.as_deref(), Some("mediaPath"));
+        assert_eq!(alert.group_index, Some(1));
+        assert_eq!(alert.time, Some(3682));
+        assert_eq!(alert.location, None);
+    }
+
+    #[test]
+    fn test_separator_sheets() {
+        let sheets = SeparatorSheets::new(SeparatorSheetsType::SlipSheets).media("iso_a4_210x297mm");
+        let value = sheets.to_value();
+        assert_eq!(
+            value,
+            IppValue::collection(vec![
+                (SEPARATOR_SHEETS_TYPE, IppValue::Keyword("slip-sheets".to_owned())),
+                (MEDIA, IppValue::Keyword("iso_a4_210x297mm".to_owned())),
+            ])
+        );
+        assert_eq!(SeparatorSheets::from_value(&value), Some(sheets.clone()));
+
+        let mut attrs = IppAttributes::new();
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                MEDIA_SUPPORTED,
+                IppValue::ListOf(vec![IppValue::Keyword("na_letter_8.5x11in".to_owned())]),
+            ),
+        );
+        let caps = PrinterCapabilities::from(attrs);
+        assert_eq!(sheets.is_supported_by(&caps), Some(false));
+        assert_eq!(
+            sheets.clone().media("na_letter_8.5x11in").is_supported_by(&caps),
+            Some(true)
+        );
+        assert_eq!(
+            SeparatorSheets::new(SeparatorSheetsType::None).is_supported_by(&caps),
+            None
+        );
+    }
+
+    #[test]
+    fn test_multiple_document_handling() {
+        for handling in &[
+            MultipleDocumentHandling::SingleDocument,
+            MultipleDocumentHandling::SeparateDocumentsCollatedCopies,
+            MultipleDocumentHandling::SeparateDocumentsUncollatedCopies,
+            MultipleDocumentHandling::SingleDocumentNewSheet,
+        ] {
+            assert_eq!(
+                MultipleDocumentHandling::from_keyword(handling.as_keyword()),
+                Some(*handling)
+            );
+        }
+        assert_eq!(MultipleDocumentHandling::from_keyword("collated"), None);
+    }
+
+    #[test]
+    fn test_printer_state_reason() {
+        let reason = PrinterStateReason::parse("toner-low-warning");
+        assert_eq!(reason.reason, "toner-low");
+        assert_eq!(reason.severity, ReasonSeverity::Warning);
+        assert_eq!(
+            PrinterStateReason::parse("media-jam-error").severity,
+            ReasonSeverity::Error
+        );
+        assert_eq!(PrinterStateReason::parse("paused").severity, ReasonSeverity::Error);
+        assert_eq!(
+            PrinterStateReason::parse("other-report").severity,
+            ReasonSeverity::Report
+        );
+    }
+
+    #[test]
+    fn test_document_attributes() {
+        let mut attrs = IppAttributes::new();
+        for (number, format) in &[(1, "application/pdf"), (2, "image/jpeg")] {
+            let mut group = IppAttributeGroup::new(DelimiterTag::DocumentAttributes);
+            group.attributes_mut().insert(
+                DOCUMENT_NUMBER.to_owned(),
+                IppAttribute::new(DOCUMENT_NUMBER, IppValue::Integer(*number)),
+            );
+            group.attributes_mut().insert(
+                DOCUMENT_FORMAT.to_owned(),
+                IppAttribute::new(DOCUMENT_FORMAT, IppValue::MimeMediaType((*format).to_owned())),
+            );
+            attrs.groups_mut().push(group);
+        }
+
+        let documents = DocumentAttributes::from_attributes(&attrs);
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].document_number, Some(1));
+        assert_eq!(documents[1].document_format.as_deref(), Some("image/jpeg"));
+        assert_eq!(documents[1].document_state, None);
+    }
+
+    #[test]
+    fn test_printer_attributes() {
+        let mut attrs = IppAttributes::new();
+        assert!(attrs.printer().is_none());
+
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_NAME, IppValue::NameWithoutLanguage("office".to_owned())),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE, IppValue::Enum(PrinterState::Processing as i32)),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(
+                DOCUMENT_FORMAT_SUPPORTED,
+                IppValue::ListOf(vec![
+                    IppValue::MimeMediaType("application/pdf".to_owned()),
+                    IppValue::MimeMediaType("image/urf".to_owned()),
+                ]),
+            ),
+        );
+        attrs.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_URI_SUPPORTED, IppValue::Uri("ipp://office/".to_owned())),
+        );
+
+        let printer = attrs.printer().unwrap();
+        assert_eq!(printer.name(), Some("office"));
+        assert_eq!(printer.state(), Some(PrinterState::Processing));
+        assert_eq!(printer.document_formats(), vec!["application/pdf", "image/urf"]);
+        assert_eq!(printer.uri_supported(), vec!["ipp://office/"]);
+        assert!(printer.state_reasons().is_empty());
+        assert_eq!(printer.is_accepting_jobs(), None);
+    }
+
+    #[test]
+    fn test_job_attributes() {
+        let mut attrs = IppAttributes::new();
+        for (id, state) in &[(1, JobState::Completed), (2, JobState::Pending)] {
+            attrs.begin_group(DelimiterTag::JobAttributes);
+            attrs.add_to_last_group(IppAttribute::new(JOB_ID, IppValue::Integer(*id)));
+            attrs.add_to_last_group(IppAttribute::new(JOB_STATE, IppValue::Enum(*state as i32)));
+        }
+
+        let jobs = attrs.jobs();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[1].id(), Some(2));
+        assert_eq!(jobs[1].state(), Some(JobState::Pending));
+        assert_eq!(jobs[0].name(), None);
+    }
+
+    #[test]
+    fn test_job_state_reasons() {
+        let mut attrs = IppAttributes::new();
+        attrs.begin_group(DelimiterTag::JobAttributes);
+        attrs.add_to_last_group(IppAttribute::new(
+            JOB_STATE_REASONS,
+            IppValue::Keyword("job-incoming".to_owned()),
+        ));
+        attrs.begin_group(DelimiterTag::JobAttributes);
+        attrs.add_to_last_group(IppAttribute::new(
+            JOB_STATE_REASONS,
+            IppValue::ListOf(vec![
+                IppValue::Keyword("job-incoming".to_owned()),
+                IppValue::Keyword("job-printing".to_owned()),
+            ]),
+        ));
+        attrs.begin_group(DelimiterTag::JobAttributes);
+
+        let jobs = attrs.jobs();
+        assert_eq!(jobs[0].state_reasons(), vec!["job-incoming"]);
+        assert_eq!(jobs[1].state_reasons(), vec!["job-incoming", "job-printing"]);
+        assert!(jobs[2].state_reasons().is_empty());
+    }
+}
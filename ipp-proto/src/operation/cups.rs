@@ -2,9 +2,15 @@
 //! CUPS-specific IPP operations
 //!
 
+#[cfg(feature = "cups")]
+use crate::attribute::{IppAttribute, DOCUMENT_NUMBER, JOB_ID, REQUESTING_USER_NAME};
+#[cfg(feature = "cups")]
+use crate::ipp::DelimiterTag;
 use crate::ipp::Operation;
 use crate::operation::IppOperation;
 use crate::request::IppRequestResponse;
+#[cfg(feature = "cups")]
+use crate::IppValue;
 
 /// IPP operation CUPS-Get-Printers
 #[derive(Default)]
@@ -13,7 +19,7 @@ pub struct CupsGetPrinters;
 impl CupsGetPrinters {
     /// Create CUPS-Get-Printers operation
     pub fn new() -> CupsGetPrinters {
-        CupsGetPrinters::default()
+        CupsGetPrinters
     }
 }
 
@@ -30,7 +36,7 @@ pub struct CupsDeletePrinter;
 impl CupsDeletePrinter {
     /// Create CUPS-Get-Printers operation
     pub fn new() -> CupsDeletePrinter {
-        CupsDeletePrinter::default()
+        CupsDeletePrinter
     }
 }
 
@@ -39,3 +45,78 @@ impl IppOperation for CupsDeletePrinter {
         IppRequestResponse::new(self.version(), Operation::CupsDeletePrinter, Some(uri))
     }
 }
+
+/// IPP operation CUPS-Get-Document
+#[cfg(feature = "cups")]
+pub struct CupsGetDocument {
+    job_id: i32,
+    document_number: i32,
+}
+
+#[cfg(feature = "cups")]
+impl CupsGetDocument {
+    /// Create CUPS-Get-Document operation
+    ///
+    /// * `job_id` - job ID<br/>
+    /// * `document_number` - 1-based number of the document within the job<br/>
+    pub fn new(job_id: i32, document_number: i32) -> CupsGetDocument {
+        CupsGetDocument {
+            job_id,
+            document_number,
+        }
+    }
+}
+
+#[cfg(feature = "cups")]
+impl IppOperation for CupsGetDocument {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::CupsGetDocument, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(self.job_id)),
+        );
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(DOCUMENT_NUMBER, IppValue::Integer(self.document_number)),
+        );
+        retval
+    }
+}
+
+/// IPP operation Purge-Jobs as implemented by CUPS, cancels and removes all jobs of the printer
+#[cfg(feature = "cups")]
+#[derive(Default)]
+pub struct PurgeJobs {
+    user_name: Option<String>,
+}
+
+#[cfg(feature = "cups")]
+impl PurgeJobs {
+    /// Create Purge-Jobs operation
+    pub fn new() -> PurgeJobs {
+        PurgeJobs::default()
+    }
+
+    /// Set requesting-user-name of the printer operator
+    pub fn user_name(mut self, user_name: &str) -> PurgeJobs {
+        self.user_name = Some(user_name.to_owned());
+        self
+    }
+}
+
+#[cfg(feature = "cups")]
+impl IppOperation for PurgeJobs {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::PurgeJobs, Some(uri));
+
+        if let Some(user_name) = self.user_name {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage(user_name)),
+            );
+        }
+
+        retval
+    }
+}
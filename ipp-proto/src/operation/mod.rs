@@ -21,6 +21,7 @@ pub struct PrintJob {
     source: IppJobSource,
     user_name: Option<String>,
     job_name: Option<String>,
+    operation_attributes: Vec<IppAttribute>,
     attributes: Vec<IppAttribute>,
 }
 
@@ -39,10 +40,16 @@ impl PrintJob {
             source,
             user_name: user_name.map(|v| v.as_ref().to_string()),
             job_name: job_name.map(|v| v.as_ref().to_string()),
+            operation_attributes: Vec::new(),
             attributes: Vec::new(),
         }
     }
 
+    /// Set extra operation attribute for this operation, for example `job-mandatory-attributes`
+    pub fn add_operation_attribute(&mut self, attribute: IppAttribute) {
+        self.operation_attributes.push(attribute);
+    }
+
     /// Set extra job attribute for this operation, for example `colormodel=grayscale`
     pub fn add_attribute(&mut self, attribute: IppAttribute) {
         self.attributes.push(attribute);
@@ -67,6 +74,12 @@ impl IppOperation for PrintJob {
             )
         }
 
+        for attr in &self.operation_attributes {
+            retval
+                .attributes_mut()
+                .add(DelimiterTag::OperationAttributes, attr.clone());
+        }
+
         for attr in &self.attributes {
             retval.attributes_mut().add(DelimiterTag::JobAttributes, attr.clone());
         }
@@ -75,6 +88,74 @@ impl IppOperation for PrintJob {
     }
 }
 
+/// IPP operation Validate-Job
+pub struct ValidateJob {
+    user_name: Option<String>,
+    job_name: Option<String>,
+    operation_attributes: Vec<IppAttribute>,
+    attributes: Vec<IppAttribute>,
+}
+
+impl ValidateJob {
+    /// Create Validate-Job operation
+    ///
+    /// * `user_name` - name of the user (requesting-user-name)<br/>
+    /// * `job_name` - job name (job-name)<br/>
+    pub fn new<U, N>(user_name: Option<U>, job_name: Option<N>) -> ValidateJob
+    where
+        U: AsRef<str>,
+        N: AsRef<str>,
+    {
+        ValidateJob {
+            user_name: user_name.map(|v| v.as_ref().to_string()),
+            job_name: job_name.map(|v| v.as_ref().to_string()),
+            operation_attributes: Vec::new(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Set extra operation attribute for this operation, for example `document-format`
+    pub fn add_operation_attribute(&mut self, attribute: IppAttribute) {
+        self.operation_attributes.push(attribute);
+    }
+
+    /// Set extra job attribute for this operation, for example `colormodel=grayscale`
+    pub fn add_attribute(&mut self, attribute: IppAttribute) {
+        self.attributes.push(attribute);
+    }
+}
+
+impl IppOperation for ValidateJob {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::ValidateJob, Some(uri));
+
+        if let Some(ref user_name) = self.user_name {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage(user_name.clone())),
+            );
+        }
+
+        if let Some(ref job_name) = self.job_name {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(JOB_NAME, IppValue::NameWithoutLanguage(job_name.clone())),
+            )
+        }
+
+        for attr in &self.operation_attributes {
+            retval
+                .attributes_mut()
+                .add(DelimiterTag::OperationAttributes, attr.clone());
+        }
+
+        for attr in &self.attributes {
+            retval.attributes_mut().add(DelimiterTag::JobAttributes, attr.clone());
+        }
+        retval
+    }
+}
+
 /// IPP operation Get-Printer-Attributes
 #[derive(Default)]
 pub struct GetPrinterAttributes {
@@ -115,9 +196,138 @@ impl IppOperation for GetPrinterAttributes {
     }
 }
 
+/// IPP operation Get-Jobs
+#[derive(Default)]
+pub struct GetJobs {
+    which_jobs: Option<String>,
+    my_jobs: Option<bool>,
+    limit: Option<i32>,
+    attributes: Vec<String>,
+}
+
+impl GetJobs {
+    /// Create Get-Jobs operation which returns not completed jobs
+    pub fn new() -> GetJobs {
+        GetJobs::default()
+    }
+
+    /// Select jobs by which-jobs keyword, for example `completed`, `not-completed` or `proof-print`
+    pub fn which_jobs(mut self, which_jobs: &str) -> GetJobs {
+        self.which_jobs = Some(which_jobs.to_owned());
+        self
+    }
+
+    /// Return only jobs submitted by the requesting user
+    pub fn my_jobs(mut self, my_jobs: bool) -> GetJobs {
+        self.my_jobs = Some(my_jobs);
+        self
+    }
+
+    /// Limit the number of returned jobs
+    pub fn limit(mut self, limit: i32) -> GetJobs {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set job attributes to request for each job
+    pub fn attributes<T>(mut self, attributes: &[T]) -> GetJobs
+    where
+        T: AsRef<str>,
+    {
+        self.attributes = attributes.iter().map(|a| a.as_ref().to_string()).collect();
+        self
+    }
+}
+
+impl IppOperation for GetJobs {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::GetJobs, Some(uri));
+
+        if let Some(which_jobs) = self.which_jobs {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(WHICH_JOBS, IppValue::Keyword(which_jobs)),
+            );
+        }
+
+        if let Some(my_jobs) = self.my_jobs {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(MY_JOBS, IppValue::Boolean(my_jobs)),
+            );
+        }
+
+        if let Some(limit) = self.limit {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(LIMIT, IppValue::Integer(limit)),
+            );
+        }
+
+        if !self.attributes.is_empty() {
+            let vals: Vec<IppValue> = self.attributes.into_iter().map(IppValue::Keyword).collect();
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTED_ATTRIBUTES, IppValue::ListOf(vals)),
+            );
+        }
+
+        retval
+    }
+}
+
+/// IPP operation Get-Job-Attributes
+pub struct GetJobAttributes {
+    job_id: i32,
+    attributes: Vec<String>,
+}
+
+impl GetJobAttributes {
+    /// Create Get-Job-Attributes operation
+    ///
+    /// * `job_id` - id of the job to query<br/>
+    pub fn new(job_id: i32) -> GetJobAttributes {
+        GetJobAttributes {
+            job_id,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Set job attributes to request, for example `job-state` and `job-impressions-completed`
+    pub fn attributes<T>(mut self, attributes: &[T]) -> GetJobAttributes
+    where
+        T: AsRef<str>,
+    {
+        self.attributes = attributes.iter().map(|a| a.as_ref().to_string()).collect();
+        self
+    }
+}
+
+impl IppOperation for GetJobAttributes {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::GetJobAttributes, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(self.job_id)),
+        );
+
+        if !self.attributes.is_empty() {
+            let vals: Vec<IppValue> = self.attributes.into_iter().map(IppValue::Keyword).collect();
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTED_ATTRIBUTES, IppValue::ListOf(vals)),
+            );
+        }
+
+        retval
+    }
+}
+
 /// IPP operation Create-Job
 pub struct CreateJob {
     job_name: Option<String>,
+    operation_attributes: Vec<IppAttribute>,
     attributes: Vec<IppAttribute>,
 }
 
@@ -131,10 +341,16 @@ impl CreateJob {
     {
         CreateJob {
             job_name: job_name.map(|v| v.as_ref().to_string()),
+            operation_attributes: Vec::new(),
             attributes: Vec::new(),
         }
     }
 
+    /// Set extra operation attribute for this operation, for example `job-mandatory-attributes`
+    pub fn add_operation_attribute(&mut self, attribute: IppAttribute) {
+        self.operation_attributes.push(attribute);
+    }
+
     /// Set extra job attribute for this operation, for example `colormodel=grayscale`
     pub fn add_attribute(&mut self, attribute: IppAttribute) {
         self.attributes.push(attribute);
@@ -152,6 +368,12 @@ impl IppOperation for CreateJob {
             )
         }
 
+        for attr in &self.operation_attributes {
+            retval
+                .attributes_mut()
+                .add(DelimiterTag::OperationAttributes, attr.clone());
+        }
+
         for attr in &self.attributes {
             retval.attributes_mut().add(DelimiterTag::JobAttributes, attr.clone());
         }
@@ -165,6 +387,7 @@ pub struct SendDocument {
     source: IppJobSource,
     user_name: Option<String>,
     last: bool,
+    operation_attributes: Vec<IppAttribute>,
 }
 
 impl SendDocument {
@@ -183,8 +406,14 @@ impl SendDocument {
             source,
             user_name: user_name.map(|v| v.as_ref().to_string()),
             last,
+            operation_attributes: Vec::new(),
         }
     }
+
+    /// Set extra operation attribute for this operation, for example `document-charset`
+    pub fn add_operation_attribute(&mut self, attribute: IppAttribute) {
+        self.operation_attributes.push(attribute);
+    }
 }
 
 impl IppOperation for SendDocument {
@@ -208,8 +437,544 @@ impl IppOperation for SendDocument {
             IppAttribute::new(LAST_DOCUMENT, IppValue::Boolean(self.last)),
         );
 
+        for attr in &self.operation_attributes {
+            retval
+                .attributes_mut()
+                .add(DelimiterTag::OperationAttributes, attr.clone());
+        }
+
         retval.add_payload(self.source);
 
         retval
     }
 }
+
+/// IPP operation Send-URI
+pub struct SendUri {
+    job_id: i32,
+    document_uri: String,
+    user_name: Option<String>,
+    last: bool,
+    operation_attributes: Vec<IppAttribute>,
+}
+
+impl SendUri {
+    /// Create Send-URI operation
+    ///
+    /// * `job_id` - job ID returned by Create-Job operation<br/>
+    /// * `document_uri` - URI of the document which the printer fetches itself<br/>
+    /// * `user_name` - name of the user (requesting-user-name)<br/>
+    /// * `last` - whether this document is a last one<br/>
+    pub fn new<S>(job_id: i32, document_uri: &str, user_name: Option<S>, last: bool) -> SendUri
+    where
+        S: AsRef<str>,
+    {
+        SendUri {
+            job_id,
+            document_uri: document_uri.to_owned(),
+            user_name: user_name.map(|v| v.as_ref().to_string()),
+            last,
+            operation_attributes: Vec::new(),
+        }
+    }
+
+    /// Set extra operation attribute for this operation, for example `document-format`
+    pub fn add_operation_attribute(&mut self, attribute: IppAttribute) {
+        self.operation_attributes.push(attribute);
+    }
+}
+
+impl IppOperation for SendUri {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::SendUri, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(self.job_id)),
+        );
+
+        if let Some(user_name) = self.user_name {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage(user_name)),
+            );
+        }
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(LAST_DOCUMENT, IppValue::Boolean(self.last)),
+        );
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(DOCUMENT_URI, IppValue::Uri(self.document_uri)),
+        );
+
+        for attr in &self.operation_attributes {
+            retval
+                .attributes_mut()
+                .add(DelimiterTag::OperationAttributes, attr.clone());
+        }
+
+        retval
+    }
+}
+
+/// IPP operation Cancel-Job
+pub struct CancelJob {
+    job_id: i32,
+    user_name: Option<String>,
+}
+
+impl CancelJob {
+    /// Create Cancel-Job operation
+    ///
+    /// * `job_id` - id of the job to cancel<br/>
+    pub fn new(job_id: i32) -> CancelJob {
+        CancelJob {
+            job_id,
+            user_name: None,
+        }
+    }
+
+    /// Set requesting-user-name, printers usually allow only the job owner to cancel the job
+    pub fn user_name(mut self, user_name: &str) -> CancelJob {
+        self.user_name = Some(user_name.to_owned());
+        self
+    }
+}
+
+impl IppOperation for CancelJob {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::CancelJob, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(self.job_id)),
+        );
+
+        if let Some(user_name) = self.user_name {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage(user_name)),
+            );
+        }
+
+        retval
+    }
+}
+
+/// IPP operation Hold-Job
+pub struct HoldJob {
+    job_id: i32,
+    user_name: Option<String>,
+    hold_until: Option<String>,
+}
+
+impl HoldJob {
+    /// Create Hold-Job operation
+    ///
+    /// * `job_id` - id of the job to hold<br/>
+    pub fn new(job_id: i32) -> HoldJob {
+        HoldJob {
+            job_id,
+            user_name: None,
+            hold_until: None,
+        }
+    }
+
+    /// Set requesting-user-name, printers usually allow only the job owner to hold the job
+    pub fn user_name(mut self, user_name: &str) -> HoldJob {
+        self.user_name = Some(user_name.to_owned());
+        self
+    }
+
+    /// Set job-hold-until keyword, for example `indefinite` (default) or `night`
+    pub fn hold_until(mut self, hold_until: &str) -> HoldJob {
+        self.hold_until = Some(hold_until.to_owned());
+        self
+    }
+}
+
+impl IppOperation for HoldJob {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::HoldJob, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(self.job_id)),
+        );
+
+        if let Some(user_name) = self.user_name {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage(user_name)),
+            );
+        }
+
+        if let Some(hold_until) = self.hold_until {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(JOB_HOLD_UNTIL, IppValue::Keyword(hold_until)),
+            );
+        }
+
+        retval
+    }
+}
+
+/// IPP operation Release-Job
+pub struct ReleaseJob {
+    job_id: i32,
+    user_name: Option<String>,
+}
+
+impl ReleaseJob {
+    /// Create Release-Job operation
+    ///
+    /// * `job_id` - id of the held job to release<br/>
+    pub fn new(job_id: i32) -> ReleaseJob {
+        ReleaseJob {
+            job_id,
+            user_name: None,
+        }
+    }
+
+    /// Set requesting-user-name
+    pub fn user_name(mut self, user_name: &str) -> ReleaseJob {
+        self.user_name = Some(user_name.to_owned());
+        self
+    }
+}
+
+impl IppOperation for ReleaseJob {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::ReleaseJob, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(self.job_id)),
+        );
+
+        if let Some(user_name) = self.user_name {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage(user_name)),
+            );
+        }
+
+        retval
+    }
+}
+
+/// IPP operation Pause-Printer
+#[derive(Default)]
+pub struct PausePrinter {
+    user_name: Option<String>,
+}
+
+impl PausePrinter {
+    /// Create Pause-Printer operation
+    pub fn new() -> PausePrinter {
+        PausePrinter::default()
+    }
+
+    /// Set requesting-user-name of the printer operator
+    pub fn user_name(mut self, user_name: &str) -> PausePrinter {
+        self.user_name = Some(user_name.to_owned());
+        self
+    }
+}
+
+impl IppOperation for PausePrinter {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::PausePrinter, Some(uri));
+
+        if let Some(user_name) = self.user_name {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage(user_name)),
+            );
+        }
+
+        retval
+    }
+}
+
+/// IPP operation Resume-Printer
+#[derive(Default)]
+pub struct ResumePrinter {
+    user_name: Option<String>,
+}
+
+impl ResumePrinter {
+    /// Create Resume-Printer operation
+    pub fn new() -> ResumePrinter {
+        ResumePrinter::default()
+    }
+
+    /// Set requesting-user-name of the printer operator
+    pub fn user_name(mut self, user_name: &str) -> ResumePrinter {
+        self.user_name = Some(user_name.to_owned());
+        self
+    }
+}
+
+impl IppOperation for ResumePrinter {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::ResumePrinter, Some(uri));
+
+        if let Some(user_name) = self.user_name {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(REQUESTING_USER_NAME, IppValue::NameWithoutLanguage(user_name)),
+            );
+        }
+
+        retval
+    }
+}
+
+/// IPP operation Create-Printer-Subscriptions with the `ippget` pull delivery method
+pub struct CreatePrinterSubscriptions {
+    events: Vec<String>,
+    lease_duration: Option<i32>,
+}
+
+impl CreatePrinterSubscriptions {
+    /// Create Create-Printer-Subscriptions operation
+    ///
+    /// * `events` - notify-events keywords, for example `printer-state-changed`<br/>
+    /// * `lease_duration` - requested lease duration in seconds, 0 for no expiration, printer default if None<br/>
+    pub fn new<T>(events: &[T], lease_duration: Option<i32>) -> CreatePrinterSubscriptions
+    where
+        T: AsRef<str>,
+    {
+        CreatePrinterSubscriptions {
+            events: events.iter().map(|e| e.as_ref().to_owned()).collect(),
+            lease_duration,
+        }
+    }
+}
+
+impl IppOperation for CreatePrinterSubscriptions {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::CreatePrinterSubscriptions, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::SubscriptionAttributes,
+            IppAttribute::new(NOTIFY_PULL_METHOD, IppValue::Keyword("ippget".to_owned())),
+        );
+
+        if !self.events.is_empty() {
+            retval.attributes_mut().add(
+                DelimiterTag::SubscriptionAttributes,
+                IppAttribute::new(
+                    NOTIFY_EVENTS,
+                    IppValue::ListOf(self.events.into_iter().map(IppValue::Keyword).collect()),
+                ),
+            );
+        }
+
+        if let Some(lease_duration) = self.lease_duration {
+            retval.attributes_mut().add(
+                DelimiterTag::SubscriptionAttributes,
+                IppAttribute::new(NOTIFY_LEASE_DURATION, IppValue::Integer(lease_duration)),
+            );
+        }
+
+        retval
+    }
+}
+
+/// IPP operation Get-Notifications
+pub struct GetNotifications {
+    subscription_id: i32,
+    sequence_number: Option<i32>,
+}
+
+impl GetNotifications {
+    /// Create Get-Notifications operation
+    ///
+    /// * `subscription_id` - subscription id returned by Create-Printer-Subscriptions<br/>
+    /// * `sequence_number` - lowest notify-sequence-number to return, all pending events if None<br/>
+    pub fn new(subscription_id: i32, sequence_number: Option<i32>) -> GetNotifications {
+        GetNotifications {
+            subscription_id,
+            sequence_number,
+        }
+    }
+}
+
+impl IppOperation for GetNotifications {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::GetNotifications, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(NOTIFY_SUBSCRIPTION_IDS, IppValue::Integer(self.subscription_id)),
+        );
+
+        if let Some(sequence_number) = self.sequence_number {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(NOTIFY_SEQUENCE_NUMBERS, IppValue::Integer(sequence_number)),
+            );
+        }
+
+        retval
+    }
+}
+
+/// IPP operation Renew-Subscription
+pub struct RenewSubscription {
+    subscription_id: i32,
+    lease_duration: Option<i32>,
+}
+
+impl RenewSubscription {
+    /// Create Renew-Subscription operation
+    ///
+    /// * `subscription_id` - subscription id returned by Create-Printer-Subscriptions or Create-Job-Subscriptions<br/>
+    /// * `lease_duration` - requested lease duration in seconds, printer default if None<br/>
+    pub fn new(subscription_id: i32, lease_duration: Option<i32>) -> RenewSubscription {
+        RenewSubscription {
+            subscription_id,
+            lease_duration,
+        }
+    }
+}
+
+impl IppOperation for RenewSubscription {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::RenewSubscription, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(NOTIFY_SUBSCRIPTION_ID, IppValue::Integer(self.subscription_id)),
+        );
+
+        if let Some(lease_duration) = self.lease_duration {
+            retval.attributes_mut().add(
+                DelimiterTag::SubscriptionAttributes,
+                IppAttribute::new(NOTIFY_LEASE_DURATION, IppValue::Integer(lease_duration)),
+            );
+        }
+
+        retval
+    }
+}
+
+/// IPP operation Cancel-Subscription
+pub struct CancelSubscription {
+    subscription_id: i32,
+}
+
+impl CancelSubscription {
+    /// Create Cancel-Subscription operation
+    ///
+    /// * `subscription_id` - subscription id returned by Create-Printer-Subscriptions or Create-Job-Subscriptions<br/>
+    pub fn new(subscription_id: i32) -> CancelSubscription {
+        CancelSubscription { subscription_id }
+    }
+}
+
+impl IppOperation for CancelSubscription {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::CancelSubscription, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(NOTIFY_SUBSCRIPTION_ID, IppValue::Integer(self.subscription_id)),
+        );
+
+        retval
+    }
+}
+
+/// IPP operation Get-Documents
+pub struct GetDocuments {
+    job_id: i32,
+}
+
+impl GetDocuments {
+    /// Create Get-Documents operation
+    ///
+    /// * `job_id` - job ID<br/>
+    pub fn new(job_id: i32) -> GetDocuments {
+        GetDocuments { job_id }
+    }
+}
+
+impl IppOperation for GetDocuments {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::GetDocuments, Some(uri));
+
+        retval.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_ID, IppValue::Integer(self.job_id)),
+        );
+
+        retval
+    }
+
+    fn version(&self) -> IppVersion {
+        IppVersion::Ipp20
+    }
+}
+
+/// IPP operation Identify-Printer which makes the printer beep or flash so that it can be located
+#[derive(Default)]
+pub struct IdentifyPrinter {
+    actions: Vec<String>,
+    message: Option<String>,
+}
+
+impl IdentifyPrinter {
+    /// Create Identify-Printer operation, the printer chooses the actions by default
+    pub fn new() -> IdentifyPrinter {
+        IdentifyPrinter::default()
+    }
+
+    /// Set identify-actions keywords, for example `&["sound", "flash"]`
+    pub fn actions<T>(mut self, actions: &[T]) -> IdentifyPrinter
+    where
+        T: AsRef<str>,
+    {
+        self.actions = actions.iter().map(|a| a.as_ref().to_owned()).collect();
+        self
+    }
+
+    /// Set message shown or spoken by the printer with the `display` or `speak` actions
+    pub fn message(mut self, message: &str) -> IdentifyPrinter {
+        self.message = Some(message.to_owned());
+        self
+    }
+}
+
+impl IppOperation for IdentifyPrinter {
+    fn into_ipp_request(self, uri: &str) -> IppRequestResponse {
+        let mut retval = IppRequestResponse::new(self.version(), Operation::IdentifyPrinter, Some(uri));
+
+        if !self.actions.is_empty() {
+            let actions = self.actions.into_iter().map(IppValue::Keyword).collect();
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(IDENTIFY_ACTIONS, IppValue::ListOf(actions)),
+            );
+        }
+
+        if let Some(message) = self.message {
+            retval.attributes_mut().add(
+                DelimiterTag::OperationAttributes,
+                IppAttribute::new(MESSAGE, IppValue::TextWithoutLanguage(message)),
+            );
+        }
+
+        retval
+    }
+
+    fn version(&self) -> IppVersion {
+        IppVersion::Ipp20
+    }
+}
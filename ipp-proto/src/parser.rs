@@ -7,11 +7,14 @@ use std::{
 };
 
 use byteorder::{BigEndian, ReadBytesExt};
+use bytes::{Bytes, BytesMut};
 use futures::{try_ready, Async, Future, Poll, Stream};
 use log::{debug, error};
 use num_traits::FromPrimitive;
+use tokio_codec::{BytesCodec, FramedRead};
+use tokio_io::AsyncRead;
 
-use crate::{ipp::*, IppAttribute, IppAttributeGroup, IppAttributes, IppHeader, IppReadExt, IppValue, PayloadKind};
+use crate::{ipp::*, IppAttribute, IppAttributeGroup, IppAttributes, IppHeader, IppValue, PayloadKind};
 
 /// Parse error enum
 #[derive(Debug)]
@@ -20,6 +23,10 @@ pub enum ParseError {
     InvalidVersion,
     InvalidCollection,
     Incomplete,
+    /// Input ended inside of an attribute name or value of the given length
+    Truncated(usize),
+    /// Name, value or message size exceeds the configured parser limit of the given number of bytes
+    LimitExceeded(usize),
     IOError(io::Error),
 }
 
@@ -35,16 +42,25 @@ impl From<io::Error> for ParseError {
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            ParseError::InvalidTag(tag) => write!(f, "Invalid tag: {}", tag),
+            ParseError::InvalidTag(tag) => write!(f, "Invalid tag {:#04x}", tag),
             ParseError::InvalidVersion => write!(f, "Invalid IPP protocol version"),
             ParseError::InvalidCollection => write!(f, "Invalid IPP collection"),
             ParseError::Incomplete => write!(f, "Incomplete IPP payload"),
-            ParseError::IOError(err) => write!(f, "{}", err.to_string()),
+            ParseError::Truncated(len) => write!(f, "Truncated IPP payload, expected {} bytes", len),
+            ParseError::LimitExceeded(limit) => write!(f, "IPP message exceeds size limit of {} bytes", limit),
+            ParseError::IOError(err) => write!(f, "{}", err),
         }
     }
 }
 
-impl std::error::Error for ParseError {}
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::IOError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 // create a single value from one-element list, list otherwise
 fn list_or_value(mut list: Vec<IppValue>) -> IppValue {
@@ -60,6 +76,11 @@ pub struct IppParseResult {
     pub header: IppHeader,
     pub attributes: IppAttributes,
     pub payload: Option<PayloadKind>,
+    /// Received message bytes, set by `IppRequestResponse::from_bytes` and by `AsyncIppParser`
+    /// with `retain_raw` enabled
+    pub raw: Option<Bytes>,
+    /// Offset of the payload within the received message
+    pub payload_offset: usize,
 }
 
 impl IppParseResult {
@@ -68,17 +89,55 @@ impl IppParseResult {
             header,
             attributes,
             payload: None,
+            raw: None,
+            payload_offset: 0,
         }
     }
+
+    /// Annotated hex dump of the header and attributes, useful for bug reports.
+    ///
+    /// * `response` - the message is a response, the header carries a status code rather than an operation<br/>
+    ///
+    /// The received bytes are dumped if they were retained, otherwise the header and attributes are serialized.
+    /// The payload is not included
+    pub fn hex_dump(&self, response: bool) -> String {
+        crate::hexdump::message_dump(
+            &self.header,
+            &self.attributes,
+            self.raw.as_deref(),
+            self.payload_offset,
+            response,
+        )
+    }
+}
+
+/// Event produced by the streaming parser
+#[derive(Clone, Debug)]
+pub enum IppParseEvent {
+    /// IPP header, always the first event
+    Header(IppHeader),
+    /// Start of the attribute group, `EndOfAttributes` is the last event
+    Delimiter(DelimiterTag),
+    /// Complete attribute belonging to the last started group
+    Attribute(IppAttribute),
 }
 
 /// IPP parser implementation
 pub struct IppParser<'a> {
     reader: &'a mut dyn Read,
-    current_group: Option<IppAttributeGroup>,
+    strict: bool,
+    max_value_size: usize,
+    max_total_size: Option<usize>,
+    // bytes consumed from the reader so far
+    consumed: usize,
+    header_read: bool,
+    finished: bool,
+    // delimiter which follows the attribute reported by the previous event
+    pending: Option<IppParseEvent>,
     last_name: Option<String>,
+    // values of the current attribute at the bottom, one frame per open collection on top.
+    // Member names are kept in the frame as MemberAttrName values preceding each member
     context: Vec<Vec<IppValue>>,
-    attributes: IppAttributes,
 }
 
 impl<'a> IppParser<'a> {
@@ -86,55 +145,117 @@ impl<'a> IppParser<'a> {
     pub fn new(reader: &'a mut dyn Read) -> IppParser<'a> {
         IppParser {
             reader,
-            current_group: None,
+            strict: false,
+            max_value_size: u16::MAX as usize,
+            max_total_size: None,
+            consumed: 0,
+            header_read: false,
+            finished: false,
+            pending: None,
             last_name: None,
             context: vec![vec![]],
-            attributes: IppAttributes::new(),
         }
     }
 
-    fn add_last_attribute(&mut self) {
-        if let Some(ref last_name) = self.last_name {
-            if let Some(val_list) = self.context.pop() {
-                if let Some(ref mut group) = self.current_group {
-                    group.attributes_mut().insert(
-                        last_name.clone(),
-                        IppAttribute::new(&last_name, list_or_value(val_list)),
-                    );
-                }
-            }
-            self.context.push(vec![]);
+    /// Fail with `ParseError::InvalidTag` on unrecognized delimiter tags. Default is false:
+    /// attributes following such a delimiter, for example of a vendor group, are collected
+    /// into a group with `DelimiterTag::UnknownAttributes` tag
+    pub fn strict(mut self, strict: bool) -> IppParser<'a> {
+        self.strict = strict;
+        self
+    }
+
+    /// Maximum length of a single attribute name or value. Default is 65535, the protocol maximum
+    pub fn max_value_size(mut self, size: usize) -> IppParser<'a> {
+        self.max_value_size = size;
+        self
+    }
+
+    /// Maximum size of the header and attributes, the payload is not included. Default is no limit.
+    /// Guards against streams with a huge number of small attributes
+    pub fn max_total_size(mut self, size: usize) -> IppParser<'a> {
+        self.max_total_size = Some(size);
+        self
+    }
+
+    // account for bytes about to be read, failing before anything is allocated
+    fn consume(&mut self, len: usize) -> Result<(), ParseError> {
+        self.consumed += len;
+        match self.max_total_size {
+            Some(max) if self.consumed > max => Err(ParseError::LimitExceeded(max)),
+            _ => Ok(()),
         }
     }
 
-    fn parse_delimiter(&mut self, tag: u8) -> Result<DelimiterTag, ParseError> {
+    fn take_last_attribute(&mut self) -> Option<IppAttribute> {
+        let last_name = self.last_name.take()?;
+        let val_list = self.context.pop().unwrap_or_default();
+        self.context.push(vec![]);
+        Some(IppAttribute::new(&last_name, list_or_value(val_list)))
+    }
+
+    fn parse_delimiter(&mut self, tag: u8) -> Result<IppParseEvent, ParseError> {
         debug!("Delimiter tag: {:0x}", tag);
 
-        let tag = DelimiterTag::from_u8(tag).ok_or_else(|| ParseError::InvalidTag(tag))?;
+        let tag = match DelimiterTag::from_u8(tag) {
+            Some(DelimiterTag::UnknownAttributes) | None if self.strict => return Err(ParseError::InvalidTag(tag)),
+            Some(DelimiterTag::UnknownAttributes) | None => {
+                debug!("Unknown delimiter tag {:#04x}, collecting into generic group", tag);
+                DelimiterTag::UnknownAttributes
+            }
+            Some(tag) => tag,
+        };
+        // a delimiter can't appear before the open collection is closed, don't return partial data
+        if self.context.len() > 1 {
+            error!("Delimiter {:?} inside of a collection", tag);
+            return Err(ParseError::InvalidCollection);
+        }
         if tag == DelimiterTag::EndOfAttributes {
-            self.add_last_attribute();
+            self.finished = true;
         }
 
-        if let Some(group) = self.current_group.take() {
-            self.attributes.groups_mut().push(group);
+        // the last attribute belongs to the group which is being closed
+        match self.take_last_attribute() {
+            Some(attribute) => {
+                self.pending = Some(IppParseEvent::Delimiter(tag));
+                Ok(IppParseEvent::Attribute(attribute))
+            }
+            None => Ok(IppParseEvent::Delimiter(tag)),
         }
+    }
 
-        self.current_group = Some(IppAttributeGroup::new(tag));
+    // read length-prefixed field, EOF inside of the field is reported with its length.
+    // The buffer grows with the data actually read, not with the declared length
+    fn read_field(&mut self) -> Result<(u16, Bytes), ParseError> {
+        self.consume(2)?;
+        let len = self.reader.read_u16::<BigEndian>()?;
+        if len as usize > self.max_value_size {
+            return Err(ParseError::LimitExceeded(self.max_value_size));
+        }
+        self.consume(len as usize)?;
 
-        Ok(tag)
+        let mut data = Vec::new();
+        (&mut *self.reader).take(len.into()).read_to_end(&mut data)?;
+        if data.len() < len as usize {
+            return Err(ParseError::Truncated(len as usize));
+        }
+        Ok((len, data.into()))
     }
 
-    fn parse_value(&mut self, tag: u8) -> Result<(), ParseError> {
+    // returns the previous attribute once a value with a new name is read
+    fn parse_value(&mut self, tag: u8) -> Result<Option<IppAttribute>, ParseError> {
         // value tag
-        let namelen = self.reader.read_u16::<BigEndian>()?;
-        let name = self.reader.read_string(namelen as usize)?;
-        let value = IppValue::read(tag, &mut self.reader)?;
+        let (namelen, name) = self.read_field()?;
+        let name = String::from_utf8_lossy(&name).into_owned();
+        let (vsize, data) = self.read_field()?;
+        let value = IppValue::read(tag, &mut io::Cursor::new(vsize.to_be_bytes()).chain(&data[..]))?;
 
         debug!("Value tag: {:0x}: {}: {}", tag, name, value);
 
+        let mut completed = None;
         if namelen > 0 {
             // single attribute or begin of array
-            self.add_last_attribute();
+            completed = self.take_last_attribute();
             // store it as a previous attribute
             self.last_name = Some(name);
         }
@@ -159,6 +280,10 @@ impl<'a> IppParser<'a> {
                     return Err(ParseError::InvalidCollection);
                 }
             }
+            if self.context.len() < 2 {
+                error!("End collection without begin collection");
+                return Err(ParseError::InvalidCollection);
+            }
             if let Some(arr) = self.context.pop() {
                 if let Some(val_list) = self.context.last_mut() {
                     val_list.push(IppValue::Collection(arr));
@@ -168,29 +293,84 @@ impl<'a> IppParser<'a> {
             // add attribute to the current collection
             val_list.push(value);
         }
-        Ok(())
+        Ok(completed)
     }
 
-    /// Parse IPP stream
-    pub fn parse(mut self) -> Result<IppParseResult, ParseError> {
-        let header = IppHeader::from_reader(self.reader)?;
-        debug!("IPP header: {:?}", header);
+    fn next_event(&mut self) -> Result<Option<IppParseEvent>, ParseError> {
+        if let Some(event) = self.pending.take() {
+            return Ok(Some(event));
+        }
+        if self.finished {
+            return Ok(None);
+        }
+        if !self.header_read {
+            self.consume(8)?;
+            let header = IppHeader::from_reader(self.reader)?;
+            debug!("IPP header: {:?}", header);
+            self.header_read = true;
+            return Ok(Some(IppParseEvent::Header(header)));
+        }
 
         loop {
+            self.consume(1)?;
             match self.reader.read_u8()? {
-                tag @ 0x01..=0x05 => {
-                    if self.parse_delimiter(tag)? == DelimiterTag::EndOfAttributes {
-                        break;
+                tag @ 0x01..=0x0f => return self.parse_delimiter(tag).map(Some),
+                tag @ 0x10..=0x4a => {
+                    if let Some(attribute) = self.parse_value(tag)? {
+                        return Ok(Some(IppParseEvent::Attribute(attribute)));
                     }
                 }
-                tag @ 0x10..=0x4a => self.parse_value(tag)?,
                 tag => {
                     return Err(ParseError::InvalidTag(tag));
                 }
             }
         }
+    }
+
+    /// Parse only the IPP header, leaving the attributes and payload in the reader unconsumed
+    pub fn parse_header_only(reader: &mut dyn Read) -> Result<IppHeader, ParseError> {
+        IppHeader::from_reader(reader)
+    }
+
+    /// Convert parser into an iterator of parse events. Attributes are reported as soon as they are
+    /// complete, without collecting the whole response in memory. The iterator stops after the
+    /// `EndOfAttributes` delimiter or the first error, the payload is left in the reader unconsumed.
+    pub fn into_events(mut self) -> impl Iterator<Item = Result<IppParseEvent, ParseError>> + 'a {
+        std::iter::from_fn(move || {
+            let event = self.next_event();
+            if event.is_err() {
+                self.finished = true;
+            }
+            event.transpose()
+        })
+    }
+
+    /// Parse IPP stream
+    pub fn parse(self) -> Result<IppParseResult, ParseError> {
+        let mut header = None;
+        let mut attributes = IppAttributes::new();
+        let mut current_group: Option<IppAttributeGroup> = None;
+
+        for event in self.into_events() {
+            match event? {
+                IppParseEvent::Header(h) => header = Some(h),
+                IppParseEvent::Delimiter(tag) => {
+                    if let Some(group) = current_group.take() {
+                        attributes.groups_mut().push(group);
+                    }
+                    current_group = Some(IppAttributeGroup::new(tag));
+                }
+                IppParseEvent::Attribute(attribute) => {
+                    if let Some(ref mut group) = current_group {
+                        group.attributes_mut().insert(attribute.name().to_owned(), attribute);
+                    }
+                }
+            }
+        }
 
-        Ok(IppParseResult::new(header, self.attributes))
+        header
+            .map(|header| IppParseResult::new(header, attributes))
+            .ok_or(ParseError::Incomplete)
     }
 }
 
@@ -199,10 +379,61 @@ enum AsyncParseState {
     Payload(IppParseResult),
 }
 
+// limits of the synchronous parser which the asynchronous parsers apply each time
+// the buffered attributes are parsed
+#[derive(Clone, Copy)]
+struct ParseLimits {
+    max_value_size: usize,
+    max_total_size: Option<usize>,
+}
+
+impl Default for ParseLimits {
+    fn default() -> ParseLimits {
+        ParseLimits {
+            max_value_size: u16::MAX as usize,
+            max_total_size: None,
+        }
+    }
+}
+
+impl ParseLimits {
+    fn parser<'a>(&self, reader: &'a mut dyn Read) -> IppParser<'a> {
+        let parser = IppParser::new(reader).max_value_size(self.max_value_size);
+        match self.max_total_size {
+            Some(size) => parser.max_total_size(size),
+            None => parser,
+        }
+    }
+}
+
 /// Asynchronous IPP parser using Streams
 pub struct AsyncIppParser<I, E> {
     state: AsyncParseState,
     stream: Box<dyn Stream<Item = I, Error = E> + Send>,
+    raw: Option<BytesMut>,
+    limits: ParseLimits,
+}
+
+impl<I, E> AsyncIppParser<I, E> {
+    /// Keep a copy of the whole received message, including the payload, in memory.
+    /// The bytes are available in `IppParseResult::raw`, for example to inspect vendor extensions
+    pub fn retain_raw(mut self) -> Self {
+        self.raw = Some(BytesMut::new());
+        self
+    }
+
+    /// Maximum length of a single attribute name or value, see `IppParser::max_value_size`
+    pub fn max_value_size(mut self, size: usize) -> Self {
+        self.limits.max_value_size = size;
+        self
+    }
+
+    /// Maximum size of the header and attributes, see `IppParser::max_total_size`.
+    /// This also bounds the data buffered until the attributes are complete
+    pub fn max_total_size(mut self, size: usize) -> Self {
+        self.limits.max_total_size = Some(size);
+        self
+    }
 }
 
 impl<I, E> Future for AsyncIppParser<I, E>
@@ -215,17 +446,21 @@ where
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         while let Some(item) = try_ready!(self.stream.poll()) {
+            if let Some(ref mut raw) = self.raw {
+                raw.extend_from_slice(item.as_ref());
+            }
             match self.state {
                 AsyncParseState::Headers(ref mut buffer) => {
                     buffer.extend_from_slice(item.as_ref());
                     let length = buffer.len() as u64;
 
                     let mut reader = io::Cursor::new(buffer);
-                    let parser = IppParser::new(&mut reader);
+                    let parser = self.limits.parser(&mut reader);
 
                     match parser.parse() {
                         Ok(mut result) => {
                             debug!("Parse ok, proceeding to payload state");
+                            result.payload_offset = reader.position() as usize;
                             if reader.position() < length {
                                 debug!("Adding residual payload from this chunk");
                                 let mut temp = tempfile::NamedTempFile::new()?;
@@ -234,7 +469,7 @@ where
                             }
                             self.state = AsyncParseState::Payload(result);
                         }
-                        Err(ParseError::Incomplete) => {
+                        Err(ParseError::Incomplete) | Err(ParseError::Truncated(_)) => {
                             debug!("Incomplete request, awaiting for more data");
                         }
                         Err(e) => {
@@ -272,18 +507,118 @@ where
                     header: result.header.clone(),
                     attributes: result.attributes.clone(),
                     payload: result.payload.take(),
+                    raw: self.raw.take().map(BytesMut::freeze),
+                    payload_offset: result.payload_offset,
                 }))
             }
         }
     }
 }
 
+impl AsyncIppParser<BytesMut, io::Error> {
+    /// Construct asynchronous parser from the `AsyncRead`, for example a file or a socket.
+    /// The reader is consumed in chunks, the payload is stored in a temporary file
+    pub fn from_reader<R>(reader: R) -> AsyncIppParser<BytesMut, io::Error>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        let stream: Box<dyn Stream<Item = BytesMut, Error = io::Error> + Send> =
+            Box::new(FramedRead::new(reader, BytesCodec::new()));
+        AsyncIppParser::from(stream)
+    }
+}
+
 impl<I, E> From<Box<dyn Stream<Item = I, Error = E> + Send>> for AsyncIppParser<I, E> {
     /// Construct asynchronous parser from the stream
     fn from(s: Box<dyn Stream<Item = I, Error = E> + Send>) -> AsyncIppParser<I, E> {
         AsyncIppParser {
             state: AsyncParseState::Headers(Vec::new()),
             stream: s,
+            raw: None,
+            limits: ParseLimits::default(),
+        }
+    }
+}
+
+/// Payload following the attributes of a message parsed by `AsyncIppStreamParser`
+pub type IppPayloadStream<E> = Box<dyn Stream<Item = Bytes, Error = E> + Send>;
+
+/// Asynchronous IPP parser which stops after the attributes and hands over the rest of the stream
+/// as the payload, so that large documents are neither buffered in memory nor stored in a temporary file
+pub struct AsyncIppStreamParser<I, E> {
+    buffer: Vec<u8>,
+    stream: Option<Box<dyn Stream<Item = I, Error = E> + Send>>,
+    limits: ParseLimits,
+}
+
+impl<I, E> AsyncIppStreamParser<I, E> {
+    /// Maximum length of a single attribute name or value, see `IppParser::max_value_size`
+    pub fn max_value_size(mut self, size: usize) -> Self {
+        self.limits.max_value_size = size;
+        self
+    }
+
+    /// Maximum size of the header and attributes, see `IppParser::max_total_size`.
+    /// This also bounds the data buffered until the attributes are complete
+    pub fn max_total_size(mut self, size: usize) -> Self {
+        self.limits.max_total_size = Some(size);
+        self
+    }
+}
+
+impl<I, E> Future for AsyncIppStreamParser<I, E>
+where
+    I: AsRef<[u8]> + 'static,
+    E: Send + 'static,
+    ParseError: From<E>,
+{
+    type Item = (IppParseResult, IppPayloadStream<E>);
+    type Error = ParseError;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let item = match self.stream {
+                Some(ref mut stream) => try_ready!(stream.poll()),
+                None => panic!("AsyncIppStreamParser polled after completion"),
+            };
+            let item = match item {
+                Some(item) => item,
+                None => return Err(ParseError::Incomplete),
+            };
+            self.buffer.extend_from_slice(item.as_ref());
+
+            let mut reader = io::Cursor::new(&self.buffer);
+            match self.limits.parser(&mut reader).parse() {
+                Ok(mut result) => {
+                    let offset = reader.position() as usize;
+                    debug!("Parse ok, streaming payload");
+                    result.payload_offset = offset;
+
+                    let residual = Bytes::from(&self.buffer[offset..]);
+                    let rest = self.stream.take().unwrap().map(|item| Bytes::from(item.as_ref()));
+                    let payload: IppPayloadStream<E> = if residual.is_empty() {
+                        Box::new(rest)
+                    } else {
+                        Box::new(futures::stream::once(Ok(residual)).chain(rest))
+                    };
+                    return Ok(Async::Ready((result, payload)));
+                }
+                Err(ParseError::Incomplete) | Err(ParseError::Truncated(_)) => {
+                    debug!("Incomplete request, awaiting for more data");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl<I, E> From<Box<dyn Stream<Item = I, Error = E> + Send>> for AsyncIppStreamParser<I, E> {
+    /// Construct streaming parser from the stream
+    fn from(s: Box<dyn Stream<Item = I, Error = E> + Send>) -> AsyncIppStreamParser<I, E> {
+        AsyncIppStreamParser {
+            buffer: Vec::new(),
+            stream: Some(s),
+            limits: ParseLimits::default(),
         }
     }
 }
@@ -304,6 +639,17 @@ mod tests {
         assert!(res.attributes.groups().is_empty());
     }
 
+    #[test]
+    fn test_parse_header_only() {
+        let data = &[1, 1, 0, 0, 0, 0, 0, 0, 3];
+        let mut cursor = Cursor::new(data);
+
+        let header = IppParser::parse_header_only(&mut cursor).unwrap();
+        assert_eq!(header.version, IppVersion::Ipp11);
+        assert_eq!(header.operation_status, 0);
+        assert_eq!(cursor.position(), 8);
+    }
+
     #[test]
     fn test_parse_single_value() {
         let data = &[
@@ -357,6 +703,270 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_multiple_groups() {
+        let data = vec![
+            1, 1, 0, 0, 0, 0, 0, 0, 9, 0x21, 0, 1, b'a', 0, 4, 0, 0, 0, 1, 9, 0x21, 0, 1, b'a', 0, 4, 0, 0, 0, 2, 0x21,
+            0, 1, b'b', 0, 4, 0, 0, 0, 3, 3,
+        ];
+        let result = IppParser::new(&mut Cursor::new(data)).parse().unwrap();
+
+        let groups = result.attributes.groups_of(DelimiterTag::DocumentAttributes);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].attributes().len(), 1);
+        assert_eq!(
+            groups[0].attributes().get("a").map(|a| a.value()),
+            Some(&IppValue::Integer(1))
+        );
+        assert_eq!(groups[1].attributes().len(), 2);
+        assert_eq!(
+            groups[1].attributes().get("b").map(|a| a.value()),
+            Some(&IppValue::Integer(3))
+        );
+    }
+
+    // encode attribute or member with the given tag, name and raw value
+    fn encode(tag: ValueTag, name: &str, value: &[u8]) -> Vec<u8> {
+        let mut data = vec![tag as u8, 0, name.len() as u8];
+        data.extend(name.as_bytes());
+        data.extend(&[0, value.len() as u8]);
+        data.extend(value);
+        data
+    }
+
+    #[test]
+    fn test_parse_nested_collection() {
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        data.extend(encode(ValueTag::BegCollection, "media-col", b""));
+        data.extend(encode(ValueTag::MemberAttrName, "", b"media-size"));
+        data.extend(encode(ValueTag::BegCollection, "", b""));
+        data.extend(encode(ValueTag::MemberAttrName, "", b"x-dimension"));
+        data.extend(encode(ValueTag::Integer, "", &21000i32.to_be_bytes()));
+        data.extend(encode(ValueTag::MemberAttrName, "", b"y-dimension"));
+        data.extend(encode(ValueTag::Integer, "", &29700i32.to_be_bytes()));
+        data.extend(encode(ValueTag::EndCollection, "", b""));
+        data.extend(encode(ValueTag::MemberAttrName, "", b"media-source"));
+        data.extend(encode(ValueTag::Keyword, "", b"main"));
+        data.extend(encode(ValueTag::EndCollection, "", b""));
+        data.extend(encode(ValueTag::Keyword, "media-ready", b"iso_a4_210x297mm"));
+        data.push(3);
+
+        let result = IppParser::new(&mut Cursor::new(data)).parse().unwrap();
+        let attrs = result.attributes.groups_of(DelimiterTag::PrinterAttributes)[0].attributes();
+
+        assert_eq!(
+            attrs.get("media-col").map(|a| a.value()),
+            Some(&IppValue::collection(vec![
+                (
+                    "media-size",
+                    IppValue::collection(vec![
+                        ("x-dimension", IppValue::Integer(21000)),
+                        ("y-dimension", IppValue::Integer(29700)),
+                    ])
+                ),
+                ("media-source", IppValue::Keyword("main".to_owned())),
+            ]))
+        );
+        assert_eq!(
+            attrs.get("media-ready").map(|a| a.value()),
+            Some(&IppValue::Keyword("iso_a4_210x297mm".to_owned()))
+        );
+
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        data.extend(encode(ValueTag::Integer, "copies", &1i32.to_be_bytes()));
+        data.extend(encode(ValueTag::EndCollection, "", b""));
+        data.push(3);
+        let result = IppParser::new(&mut Cursor::new(data)).parse();
+        assert!(matches!(result.err(), Some(ParseError::InvalidCollection)));
+    }
+
+    #[test]
+    fn test_parse_events() {
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        data.extend(encode(ValueTag::Integer, "a", &1i32.to_be_bytes()));
+        data.extend(encode(ValueTag::Integer, "", &2i32.to_be_bytes()));
+        data.extend(encode(ValueTag::Keyword, "b", b"key"));
+        data.push(3);
+        data.extend(b"payload");
+        let mut cursor = Cursor::new(data);
+
+        let events = IppParser::new(&mut cursor)
+            .into_events()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let summary = events
+            .iter()
+            .map(|e| match e {
+                IppParseEvent::Header(h) => format!("header {:?}", h.version),
+                IppParseEvent::Delimiter(tag) => format!("delimiter {:?}", tag),
+                IppParseEvent::Attribute(a) => format!("{}={}", a.name(), a.value()),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                "header Ipp11",
+                "delimiter PrinterAttributes",
+                "a=[1, 2]",
+                "b=key",
+                "delimiter EndOfAttributes",
+            ]
+        );
+        assert_eq!(cursor.position(), cursor.get_ref().len() as u64 - 7);
+
+        let mut cursor = Cursor::new(vec![1, 1, 0, 0, 0, 0, 0, 0, 4, 0x21, 0, 1]);
+        let mut events = IppParser::new(&mut cursor).into_events();
+        assert!(events.next().unwrap().is_ok());
+        assert!(events.next().unwrap().is_ok());
+        assert!(matches!(events.next(), Some(Err(ParseError::Truncated(1)))));
+        assert!(events.next().is_none());
+    }
+
+    #[test]
+    fn test_parse_truncated_value() {
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        data.extend(&[ValueTag::RangeOfInteger as u8, 0, 5]);
+        data.extend(b"range");
+        data.extend(&[0, 8, 0, 0, 1]);
+        let result = IppParser::new(&mut Cursor::new(data)).parse();
+        assert!(matches!(result.err(), Some(ParseError::Truncated(8))));
+
+        let data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4, 0x21];
+        let result = IppParser::new(&mut Cursor::new(data)).parse();
+        assert!(matches!(result.err(), Some(ParseError::Incomplete)));
+    }
+
+    #[test]
+    fn test_parse_unterminated_collection() {
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        data.extend(encode(ValueTag::BegCollection, "media-col", b""));
+        data.extend(encode(ValueTag::MemberAttrName, "", b"media-source"));
+        data.extend(encode(ValueTag::Keyword, "", b"main"));
+        data.push(3);
+        data.extend(encode(ValueTag::Keyword, "media-ready", b"iso_a4_210x297mm"));
+
+        let result = IppParser::new(&mut Cursor::new(data)).parse();
+        assert!(matches!(result.err(), Some(ParseError::InvalidCollection)));
+    }
+
+    #[test]
+    fn test_parse_vendor_group() {
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        data.extend(encode(ValueTag::Keyword, "printer-name", b"foo"));
+        data.push(0x0e);
+        data.extend(encode(ValueTag::Keyword, "vendor-mode", b"eco"));
+        data.push(4);
+        data.extend(encode(ValueTag::Keyword, "printer-info", b"bar"));
+        data.push(3);
+
+        let result = IppParser::new(&mut Cursor::new(data.clone())).parse().unwrap();
+        let tags: Vec<DelimiterTag> = result.attributes.groups().iter().map(|g| g.tag()).collect();
+        assert_eq!(
+            tags,
+            vec![
+                DelimiterTag::PrinterAttributes,
+                DelimiterTag::UnknownAttributes,
+                DelimiterTag::PrinterAttributes
+            ]
+        );
+        let vendor = &result.attributes.groups_of(DelimiterTag::UnknownAttributes)[0];
+        assert_eq!(
+            vendor.attributes().get("vendor-mode").map(|a| a.value()),
+            Some(&IppValue::Keyword("eco".to_owned()))
+        );
+
+        let result = IppParser::new(&mut Cursor::new(data)).strict(true).parse();
+        assert!(matches!(result.err(), Some(ParseError::InvalidTag(0x0e))));
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        // value of 65535 bytes is declared but absent
+        let mut data = vec![
+            1,
+            1,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            4,
+            ValueTag::OctetStringUnspecified as u8,
+            0,
+            1,
+            b'a',
+        ];
+        data.extend(&[0xff, 0xff]);
+        let result = IppParser::new(&mut Cursor::new(data.clone())).parse();
+        assert!(matches!(result.err(), Some(ParseError::Truncated(0xffff))));
+
+        let result = IppParser::new(&mut Cursor::new(data)).max_value_size(1024).parse();
+        assert!(matches!(result.err(), Some(ParseError::LimitExceeded(1024))));
+
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        for _ in 0..100 {
+            data.extend(encode(ValueTag::Integer, "", &1i32.to_be_bytes()));
+        }
+        data.push(3);
+        assert!(IppParser::new(&mut Cursor::new(data.clone()))
+            .max_total_size(data.len())
+            .parse()
+            .is_ok());
+
+        let result = IppParser::new(&mut Cursor::new(data)).max_total_size(64).parse();
+        assert!(matches!(result.err(), Some(ParseError::LimitExceeded(64))));
+    }
+
+    #[test]
+    fn test_parse_error_display() {
+        use std::error::Error;
+
+        assert_eq!(ParseError::InvalidTag(0x4b).to_string(), "Invalid tag 0x4b");
+        assert_eq!(ParseError::InvalidTag(0x05).to_string(), "Invalid tag 0x05");
+        assert!(ParseError::Incomplete.source().is_none());
+
+        let error = ParseError::from(io::Error::new(io::ErrorKind::ConnectionReset, "reset"));
+        assert_eq!(error.to_string(), "reset");
+        assert_eq!(error.source().map(|e| e.to_string()), Some("reset".to_owned()));
+    }
+
+    #[test]
+    fn test_explicit_group_order() {
+        use crate::{ipp::Operation, IppAttribute, IppRequestResponse};
+
+        let mut request = IppRequestResponse::new(IppVersion::Ipp20, Operation::SendDocument, Some("ipp://localhost"));
+        request.begin_group(DelimiterTag::DocumentAttributes);
+        request.add(IppAttribute::new("document-name", IppValue::Keyword("a".to_owned())));
+        request.begin_group(DelimiterTag::JobAttributes);
+        request.add(IppAttribute::new("copies", IppValue::Integer(2)));
+        request.begin_group(DelimiterTag::DocumentAttributes);
+        request.add(IppAttribute::new("document-name", IppValue::Keyword("b".to_owned())));
+
+        let mut data = Vec::new();
+        request.write(&mut data).unwrap();
+        let result = IppParser::new(&mut Cursor::new(data)).parse().unwrap();
+
+        let tags = result.attributes.groups().iter().map(|g| g.tag()).collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            vec![
+                DelimiterTag::OperationAttributes,
+                DelimiterTag::DocumentAttributes,
+                DelimiterTag::JobAttributes,
+                DelimiterTag::DocumentAttributes,
+            ]
+        );
+        let names = result
+            .attributes
+            .groups_of(DelimiterTag::DocumentAttributes)
+            .iter()
+            .filter_map(|g| g.attributes().get("document-name"))
+            .map(|a| a.value().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["a", "b"]);
+    }
+
     #[test]
     fn test_async_parser_with_payload() {
         // split IPP into arbitrary chunks
@@ -393,4 +1003,117 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_async_parser_from_reader() {
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        data.extend(encode(ValueTag::Integer, "test", &0x1234_5678i32.to_be_bytes()));
+        data.push(3);
+        data.extend(b"foo");
+
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let res = runtime
+            .block_on(AsyncIppParser::from_reader(Cursor::new(data)))
+            .unwrap();
+
+        let attrs = res.attributes.groups_of(DelimiterTag::PrinterAttributes)[0].attributes();
+        assert_eq!(
+            attrs.get("test").map(|a| a.value()),
+            Some(&IppValue::Integer(0x1234_5678))
+        );
+        match res.payload {
+            Some(PayloadKind::ReceivedData(f)) => assert_eq!(std::fs::read_to_string(f.path()).unwrap(), "foo"),
+            _ => panic!("Wrong payload!"),
+        }
+    }
+
+    #[test]
+    fn test_async_parser_retain_raw() {
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 3];
+        data.extend(b"foo");
+
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let res = runtime
+            .block_on(AsyncIppParser::from_reader(Cursor::new(data.clone())).retain_raw())
+            .unwrap();
+        assert_eq!(res.raw.as_deref(), Some(&data[..]));
+        assert_eq!(res.payload_offset, 9);
+
+        let res = runtime
+            .block_on(AsyncIppParser::from_reader(Cursor::new(data)))
+            .unwrap();
+        assert!(res.raw.is_none());
+    }
+
+    #[test]
+    fn test_stream_parser() {
+        let data = vec![
+            vec![1, 1, 0],
+            vec![0, 0, 0, 0, 0, 4],
+            vec![
+                0x21, 0x00, 0x04, b't', b'e', b's', b't', 0x00, 0x04, 0x12, 0x34, 0x56, 0x78, 3, b'f',
+            ],
+            vec![b'o', b'o'],
+        ];
+        let source: Box<dyn Stream<Item = Vec<u8>, Error = io::Error> + Send> =
+            Box::new(futures::stream::iter_ok::<_, io::Error>(data));
+
+        let (res, payload) = AsyncIppStreamParser::from(source).wait().unwrap();
+        let attrs = res.attributes.groups_of(DelimiterTag::PrinterAttributes)[0].attributes();
+        assert_eq!(
+            attrs.get("test").map(|a| a.value()),
+            Some(&IppValue::Integer(0x1234_5678))
+        );
+        assert!(res.payload.is_none());
+        assert_eq!(res.payload_offset, 23);
+        assert_eq!(&payload.concat2().wait().unwrap()[..], b"foo");
+
+        let source: Box<dyn Stream<Item = Vec<u8>, Error = io::Error> + Send> =
+            Box::new(futures::stream::iter_ok::<_, io::Error>(vec![vec![1, 1, 0, 0]]));
+        assert!(matches!(
+            AsyncIppStreamParser::from(source).wait(),
+            Err(ParseError::Incomplete)
+        ));
+    }
+
+    #[test]
+    fn test_async_parser_limits() {
+        // attributes arrive one per chunk and never end
+        let chunks = || {
+            let mut data = vec![vec![1, 1, 0, 0, 0, 0, 0, 0, 4]];
+            data.extend((0..100).map(|_| encode(ValueTag::Integer, "", &1i32.to_be_bytes())));
+            let source: Box<dyn Stream<Item = Vec<u8>, Error = io::Error> + Send> =
+                Box::new(futures::stream::iter_ok::<_, io::Error>(data));
+            source
+        };
+        assert!(matches!(
+            AsyncIppParser::from(chunks()).max_total_size(64).wait(),
+            Err(ParseError::LimitExceeded(64))
+        ));
+        assert!(matches!(
+            AsyncIppStreamParser::from(chunks()).max_total_size(64).wait(),
+            Err(ParseError::LimitExceeded(64))
+        ));
+        assert!(matches!(
+            AsyncIppParser::from(chunks()).wait(),
+            Err(ParseError::Incomplete)
+        ));
+
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        data.extend(encode(ValueTag::Keyword, "test", &[b'a'; 100]));
+        data.push(3);
+        let source = || {
+            let source: Box<dyn Stream<Item = Vec<u8>, Error = io::Error> + Send> =
+                Box::new(futures::stream::iter_ok::<_, io::Error>(vec![data.clone()]));
+            source
+        };
+        assert!(matches!(
+            AsyncIppParser::from(source()).max_value_size(10).wait(),
+            Err(ParseError::LimitExceeded(10))
+        ));
+        assert!(matches!(
+            AsyncIppStreamParser::from(source()).max_value_size(10).wait(),
+            Err(ParseError::LimitExceeded(10))
+        ));
+        assert!(AsyncIppParser::from(source()).max_value_size(100).wait().is_ok());
+    }
 }
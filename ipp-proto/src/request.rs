@@ -1,7 +1,7 @@
 //!
 //! IPP request
 //!
-use std::io::{self, Cursor, Write};
+use std::io::{self, Cursor, Read, Write};
 
 use bytes::Bytes;
 use enum_as_inner::EnumAsInner;
@@ -12,7 +12,7 @@ use tempfile::NamedTempFile;
 use crate::{
     attribute::*,
     ipp::{DelimiterTag, IppVersion, Operation},
-    parser::IppParseResult,
+    parser::{IppParseResult, IppParser, ParseError},
     value::*,
     IppHeader, IppJobSource, IppWriter, StatusCode,
 };
@@ -34,16 +34,36 @@ pub struct IppRequestResponse {
     attributes: IppAttributes,
     /// Optional payload after IPP-encoded stream (for example binary data for Print-Job operation)
     payload: Option<PayloadKind>,
+    /// Received message bytes, if retained by the parser
+    raw: Option<Bytes>,
+    /// Offset of the payload within the received message
+    payload_offset: usize,
+    /// Created as a request, which must name its target
+    is_request: bool,
 }
 
 impl IppRequestResponse {
-    /// Create new IPP request for the operation and uri
+    /// Create new IPP request for the operation and uri, request-id is set to 1
     pub fn new(version: IppVersion, operation: Operation, uri: Option<&str>) -> IppRequestResponse {
-        let hdr = IppHeader::new(version, operation as u16, 1);
+        IppRequestResponse::with_request_id(version, operation, uri, 1)
+    }
+
+    /// Create new IPP request for the operation and uri with the given request-id,
+    /// for example to reuse the id of the original request when retrying
+    pub fn with_request_id(
+        version: IppVersion,
+        operation: Operation,
+        uri: Option<&str>,
+        id: u32,
+    ) -> IppRequestResponse {
+        let hdr = IppHeader::new(version, operation as u16, id);
         let mut retval = IppRequestResponse {
             header: hdr,
             attributes: IppAttributes::new(),
             payload: None,
+            raw: None,
+            payload_offset: 0,
+            is_request: true,
         };
 
         retval.attributes_mut().add(
@@ -72,6 +92,9 @@ impl IppRequestResponse {
             header: hdr,
             attributes: IppAttributes::new(),
             payload: None,
+            raw: None,
+            payload_offset: 0,
+            is_request: false,
         };
 
         retval.attributes_mut().add(
@@ -92,9 +115,24 @@ impl IppRequestResponse {
             header: result.header,
             attributes: result.attributes,
             payload: result.payload,
+            raw: result.raw,
+            payload_offset: result.payload_offset,
+            is_request: false,
         }
     }
 
+    /// Parse complete request or response from a byte slice, for example a captured message.
+    ///
+    /// The message bytes are retained: data following the attributes is available
+    /// from `payload_bytes`, `payload` is not set
+    pub fn from_bytes(data: &[u8]) -> Result<IppRequestResponse, ParseError> {
+        let mut reader = Cursor::new(data);
+        let mut result = IppParser::new(&mut reader).parse()?;
+        result.payload_offset = reader.position() as usize;
+        result.raw = Some(Bytes::from(data));
+        Ok(IppRequestResponse::from_parse_result(result))
+    }
+
     /// Get IPP header
     pub fn header(&self) -> &IppHeader {
         &self.header
@@ -105,6 +143,11 @@ impl IppRequestResponse {
         &mut self.header
     }
 
+    /// Set request-id
+    pub fn set_request_id(&mut self, id: u32) {
+        self.header.request_id = id;
+    }
+
     /// Get attributes
     pub fn attributes(&self) -> &IppAttributes {
         &self.attributes
@@ -115,6 +158,19 @@ impl IppRequestResponse {
         &mut self.attributes
     }
 
+    /// Start a new attribute group for subsequent `add` calls.
+    ///
+    /// Groups started this way are serialized in the order of creation, which gives full control over
+    /// the wire layout, for example to place document attributes before job attributes.
+    pub fn begin_group(&mut self, tag: DelimiterTag) {
+        self.attributes.begin_group(tag);
+    }
+
+    /// Add attribute to the most recently started group
+    pub fn add(&mut self, attribute: IppAttribute) {
+        self.attributes.add_to_last_group(attribute);
+    }
+
     /// Get payload
     pub fn payload(&self) -> &Option<PayloadKind> {
         &self.payload
@@ -125,13 +181,84 @@ impl IppRequestResponse {
         &mut self.payload
     }
 
+    /// Raw received message: header, attributes and payload as read from the wire.
+    /// Only available for messages created with `from_bytes` or parsed by `AsyncIppParser`
+    /// with `retain_raw` enabled
+    pub fn raw_bytes(&self) -> Option<&[u8]> {
+        self.raw.as_deref()
+    }
+
+    /// Undecoded payload following the attributes in the received message.
+    /// Only populated for operations which carry trailing data (for example CUPS-Get-Document)
+    /// and if the raw message is retained, see `raw_bytes`
+    pub fn payload_bytes(&self) -> Option<&[u8]> {
+        self.raw
+            .as_ref()
+            .map(|raw| &raw[self.payload_offset..])
+            .filter(|payload| !payload.is_empty())
+    }
+
     /// Set payload
     pub fn add_payload(&mut self, payload: IppJobSource) {
         self.payload = Some(PayloadKind::JobSource(payload))
     }
 
-    /// Serialize request into the binary stream (TCP)
+    /// Annotated hex dump of the header and attributes, useful for bug reports.
+    ///
+    /// * `response` - the message is a response, the header carries a status code rather than an operation<br/>
+    ///
+    /// The received bytes are dumped if they were retained, otherwise the header and attributes are serialized.
+    /// The payload is not included
+    pub fn hex_dump(&self, response: bool) -> String {
+        crate::hexdump::message_dump(
+            &self.header,
+            &self.attributes,
+            self.raw.as_deref(),
+            self.payload_offset,
+            response,
+        )
+    }
+
+    /// Total length of the serialized request in bytes: header, attributes and payload.
+    ///
+    /// Returns None if the payload length is not known in advance.
+    pub fn content_length(&self) -> Option<u64> {
+        let payload = match self.payload {
+            None => 0,
+            Some(PayloadKind::JobSource(ref source)) => source.length()?,
+            Some(PayloadKind::ReceivedData(ref file)) => file.as_file().metadata().ok()?.len(),
+        };
+        let mut sink = io::sink();
+        let size = self.header.write(&mut sink).ok()? + self.attributes.write(&mut sink).ok()?;
+        Some(size as u64 + payload)
+    }
+
+    // attributes-charset and attributes-natural-language are required in every message, requests
+    // created with `new` also need printer-uri or job-uri unless they are CUPS operations.
+    // Header attributes are always written first, in this order
+    fn check_required(&self) -> io::Result<()> {
+        let group = self.attributes.groups_of(DelimiterTag::OperationAttributes);
+        let has = |name: &str| group.first().is_some_and(|g| g.attributes().contains_key(name));
+
+        let mut required = vec![ATTRIBUTES_CHARSET, ATTRIBUTES_NATURAL_LANGUAGE];
+        if self.is_request && self.header.operation_status < Operation::CupsGetDefault as u16 && !has(JOB_URI) {
+            required.push(PRINTER_URI);
+        }
+        match required.into_iter().find(|name| !has(name)) {
+            Some(name) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Missing required operation attribute {}", name),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Serialize request into the binary stream (TCP).
+    ///
+    /// Fails with `InvalidInput` if a required operation attribute is missing,
+    /// for example attributes-charset or printer-uri
     pub fn write(&mut self, writer: &mut dyn Write) -> io::Result<usize> {
+        self.check_required()?;
         let mut retval = self.header.write(writer)?;
 
         retval += self.attributes.write(writer)?;
@@ -141,19 +268,260 @@ impl IppRequestResponse {
         Ok(retval)
     }
 
+    /// Serialize the whole message into a buffer: header, attributes and payload.
+    ///
+    /// The payload of a received message is included, either from the temporary file or from
+    /// the retained message bytes. A job source payload can only be streamed once, messages carrying
+    /// one fail with `InvalidInput`, use `into_stream` to send them
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        self.check_required()?;
+        let mut buf = Vec::new();
+        self.header.write(&mut buf)?;
+        self.attributes.write(&mut buf)?;
+
+        match self.payload {
+            Some(PayloadKind::JobSource(_)) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "job source payload cannot be serialized",
+                ));
+            }
+            Some(PayloadKind::ReceivedData(ref file)) => {
+                file.reopen()?.read_to_end(&mut buf)?;
+            }
+            None => {
+                if let Some(payload) = self.payload_bytes() {
+                    buf.extend_from_slice(payload);
+                }
+            }
+        }
+        Ok(buf)
+    }
+
     /// Convert request/response into Stream
     pub fn into_stream(self) -> Box<dyn Stream<Item = Bytes, Error = io::Error> + Send + 'static> {
         let mut cursor = Cursor::new(Vec::with_capacity(1024));
-        let _ = self
-            .header
-            .write(&mut cursor)
+        let result = self
+            .check_required()
+            .and_then(|_| self.header.write(&mut cursor))
             .and_then(|_| self.attributes.write(&mut cursor));
 
-        let headers = futures::stream::once(Ok(cursor.into_inner().into()));
+        // encoding errors such as oversized values or missing required attributes fail the stream
+        let headers = futures::stream::once(result.map(|_| cursor.into_inner().into()));
 
         match self.payload {
             Some(PayloadKind::JobSource(payload)) => Box::new(headers.chain(payload)),
             _ => Box::new(headers),
         }
     }
+
+    /// Convert request/response into a blocking reader which produces the same data as `into_stream`.
+    /// The payload is read on the calling thread, so it must be readable without a runtime
+    pub fn into_reader(self) -> impl Read + Send + 'static {
+        StreamReader {
+            chunks: self.into_stream().wait(),
+            chunk: Bytes::new(),
+        }
+    }
+}
+
+// blocking reader over a stream of chunks, keeps the rest of a chunk larger than the read buffer
+struct StreamReader<S> {
+    chunks: futures::stream::Wait<S>,
+    chunk: Bytes,
+}
+
+impl<S> Read for StreamReader<S>
+where
+    S: Stream<Item = Bytes, Error = io::Error>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self.chunks.next() {
+                Some(chunk) => self.chunk = chunk?,
+                None => return Ok(0),
+            }
+        }
+        let size = buf.len().min(self.chunk.len());
+        buf[..size].copy_from_slice(&self.chunk.split_to(size));
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_id() {
+        let mut request =
+            IppRequestResponse::with_request_id(IppVersion::Ipp11, Operation::GetJobs, Some("ipp://localhost"), 0x1234);
+        let mut buf = Vec::new();
+        request.header().write(&mut buf).unwrap();
+        assert_eq!(&buf[4..8], &[0x00, 0x00, 0x12, 0x34]);
+
+        request.set_request_id(0xdead_beef);
+        let mut buf = Vec::new();
+        request.write(&mut buf).unwrap();
+        assert_eq!(&buf[4..8], &[0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn test_raw_bytes() {
+        let request = IppRequestResponse::new(IppVersion::Ipp11, Operation::GetJobs, None);
+        assert!(request.raw_bytes().is_none());
+        assert!(request.payload_bytes().is_none());
+
+        let data = [1, 1, 0, 0, 0, 0, 0, 0, 3, 0xde, 0xad];
+        let response = IppRequestResponse::from_parse_result(IppParseResult {
+            header: IppHeader::new(IppVersion::Ipp11, 0, 0),
+            attributes: IppAttributes::new(),
+            payload: None,
+            raw: Some(Bytes::from(&data[..])),
+            payload_offset: 9,
+        });
+        assert_eq!(response.raw_bytes(), Some(&data[..]));
+        assert_eq!(response.payload_bytes(), Some(&[0xde, 0xad][..]));
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let mut request =
+            IppRequestResponse::with_request_id(IppVersion::Ipp20, Operation::PrintJob, Some("ipp://localhost"), 7);
+        request.attributes_mut().add(
+            DelimiterTag::JobAttributes,
+            IppAttribute::new("copies", IppValue::Integer(2)),
+        );
+        let mut data = Vec::new();
+        request.write(&mut data).unwrap();
+        data.extend_from_slice(b"%PDF-1.4");
+
+        let parsed = IppRequestResponse::from_bytes(&data).unwrap();
+        assert_eq!(parsed.header().version, IppVersion::Ipp20);
+        assert_eq!(parsed.header().operation_status, Operation::PrintJob as u16);
+        assert_eq!(parsed.header().request_id, 7);
+        assert_eq!(parsed.attributes(), request.attributes());
+        assert_eq!(parsed.payload_bytes(), Some(&b"%PDF-1.4"[..]));
+        assert_eq!(parsed.raw_bytes(), Some(&data[..]));
+
+        assert!(matches!(
+            IppRequestResponse::from_bytes(&data[..5]),
+            Err(ParseError::Incomplete)
+        ));
+    }
+
+    #[test]
+    fn test_to_bytes() {
+        let mut request = IppRequestResponse::new(IppVersion::Ipp11, Operation::GetJobs, Some("ipp://localhost"));
+        request.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new("limit", IppValue::Integer(10)),
+        );
+        let data = request.to_bytes().unwrap();
+        let parsed = IppRequestResponse::from_bytes(&data).unwrap();
+        assert_eq!(parsed.header().operation_status, Operation::GetJobs as u16);
+        assert_eq!(parsed.attributes(), request.attributes());
+        assert!(parsed.payload_bytes().is_none());
+
+        // several job groups, created with begin_group
+        let mut response = IppRequestResponse::new_response(IppVersion::Ipp11, StatusCode::SuccessfulOK, 1);
+        for id in 1..=2 {
+            response.begin_group(DelimiterTag::JobAttributes);
+            response.add(IppAttribute::new("job-id", IppValue::Integer(id)));
+        }
+        let parsed = IppRequestResponse::from_bytes(&response.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed.attributes().groups_of(DelimiterTag::JobAttributes).len(), 2);
+        assert_eq!(parsed.attributes(), response.attributes());
+
+        // document group added with the default order
+        let mut response = IppRequestResponse::new_response(IppVersion::Ipp11, StatusCode::SuccessfulOK, 1);
+        response.attributes_mut().add(
+            DelimiterTag::DocumentAttributes,
+            IppAttribute::new("document-number", IppValue::Integer(1)),
+        );
+        let parsed = IppRequestResponse::from_bytes(&response.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed.attributes(), response.attributes());
+
+        // only header attributes, written in a fixed order, so the encoding is identical
+        let mut data = Vec::new();
+        IppRequestResponse::new(IppVersion::Ipp11, Operation::PrintJob, Some("ipp://localhost"))
+            .write(&mut data)
+            .unwrap();
+        data.extend_from_slice(b"payload");
+        let parsed = IppRequestResponse::from_bytes(&data).unwrap();
+        assert_eq!(parsed.to_bytes().unwrap(), data);
+
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(b"payload").unwrap();
+        let mut received = IppRequestResponse::new(IppVersion::Ipp11, Operation::PrintJob, Some("ipp://localhost"));
+        received.payload = Some(PayloadKind::ReceivedData(temp));
+        assert_eq!(received.to_bytes().unwrap(), data);
+
+        request.add_payload(IppJobSource::from(Cursor::new(b"data".to_vec())));
+        assert_eq!(
+            request.to_bytes().map_err(|e| e.kind()).err(),
+            Some(io::ErrorKind::InvalidInput)
+        );
+    }
+
+    #[test]
+    fn test_into_reader() {
+        let document = (0..100_000).map(|i| i as u8).collect::<Vec<_>>();
+        let mut request = IppRequestResponse::new(IppVersion::Ipp11, Operation::PrintJob, Some("ipp://localhost"));
+        let mut expected = request.to_bytes().unwrap();
+        expected.extend_from_slice(&document);
+
+        request.add_payload(IppJobSource::from(Cursor::new(document)));
+        let mut data = Vec::new();
+        let mut reader = request.into_reader();
+        let mut buf = [0; 1000];
+        loop {
+            match reader.read(&mut buf).unwrap() {
+                0 => break,
+                size => data.extend_from_slice(&buf[..size]),
+            }
+        }
+        assert_eq!(data, expected);
+
+        let request = IppRequestResponse::new(IppVersion::Ipp11, Operation::GetJobs, None);
+        assert!(request.into_reader().read_to_end(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_required_attributes() {
+        let error = |request: &mut IppRequestResponse| request.write(&mut Vec::new()).unwrap_err().to_string();
+
+        let mut request = IppRequestResponse::new(IppVersion::Ipp11, Operation::GetJobs, Some("ipp://localhost"));
+        request.attributes_mut().groups_mut()[0]
+            .attributes_mut()
+            .remove(ATTRIBUTES_CHARSET);
+        assert_eq!(
+            error(&mut request),
+            "Missing required operation attribute attributes-charset"
+        );
+        assert!(request.to_bytes().is_err());
+        assert!(futures::Future::wait(futures::Stream::concat2(request.into_stream())).is_err());
+
+        let mut request = IppRequestResponse::new(IppVersion::Ipp11, Operation::GetJobs, None);
+        assert_eq!(error(&mut request), "Missing required operation attribute printer-uri");
+
+        request.attributes_mut().add(
+            DelimiterTag::OperationAttributes,
+            IppAttribute::new(JOB_URI, IppValue::Uri("ipp://localhost/jobs/1".to_owned())),
+        );
+        assert!(request.write(&mut Vec::new()).is_ok());
+
+        let mut request = IppRequestResponse::new(IppVersion::Ipp11, Operation::CupsGetPrinters, None);
+        assert!(request.write(&mut Vec::new()).is_ok());
+
+        let mut response = IppRequestResponse::new_response(IppVersion::Ipp11, StatusCode::SuccessfulOK, 1);
+        assert!(response.write(&mut Vec::new()).is_ok());
+        response.attributes_mut().groups_mut()[0]
+            .attributes_mut()
+            .remove(ATTRIBUTES_NATURAL_LANGUAGE);
+        assert_eq!(
+            error(&mut response),
+            "Missing required operation attribute attributes-natural-language"
+        );
+    }
 }
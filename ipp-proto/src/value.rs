@@ -4,6 +4,7 @@
 use std::{
     fmt,
     io::{self, Read, Write},
+    ops::Range,
 };
 
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
@@ -11,16 +12,38 @@ use bytes::Bytes;
 use enum_as_inner::EnumAsInner;
 use num_traits::FromPrimitive;
 
-use crate::{ipp::ValueTag, IppReadExt, IppWriter};
+use crate::{
+    ipp::{Orientation, PrintQuality, ValueTag},
+    model::{MediaSize, Sides},
+    IppReadExt, IppWriter,
+};
 
 /// IPP value enumeration
-#[derive(Clone, Debug, PartialEq, EnumAsInner)]
+///
+/// With the `serde` feature values are serialized as `{"type": "integer", "value": 42}`,
+/// the payload of `Other` values is base64-encoded.
+#[derive(Clone, Debug, PartialEq, Eq, EnumAsInner)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "camelCase")
+)]
 pub enum IppValue {
     Integer(i32),
     Enum(i32),
     OctetString(String),
     TextWithoutLanguage(String),
     NameWithoutLanguage(String),
+    /// Text with an explicit natural language, for example `printer-info` in a localized printer
+    TextWithLanguage {
+        lang: String,
+        text: String,
+    },
+    /// Name with an explicit natural language
+    NameWithLanguage {
+        lang: String,
+        name: String,
+    },
     Charset(String),
     NaturalLanguage(String),
     Uri(String),
@@ -51,13 +74,286 @@ pub enum IppValue {
         feed: i32,
         units: i8,
     },
+    /// Out-of-band `unsupported` value: the printer does not support the requested attribute or value
+    Unsupported,
+    /// Out-of-band `unknown` value: the attribute is supported but its value is unknown
+    Unknown,
+    /// Out-of-band `no-value` value: the attribute is supported but has no value configured
+    NoValue,
     Other {
         tag: u8,
+        #[cfg_attr(feature = "serde", serde(with = "base64_bytes"))]
         data: Bytes,
     },
 }
 
+#[cfg(feature = "serde")]
+mod base64_bytes {
+    use bytes::Bytes;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &Bytes, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::encode(data))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Bytes, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        base64::decode(&s).map(Bytes::from).map_err(D::Error::custom)
+    }
+}
+
+/// Error returned by the `expect_*` accessors when a value has a different type than expected
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeMismatch {
+    /// Tag of the expected value
+    pub expected: ValueTag,
+    /// Tag of the actual value, for lists the tag of the first element
+    pub actual: ValueTag,
+    /// Actual value is a list
+    pub list: bool,
+}
+
+impl fmt::Display for TypeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let list = if self.list { "1setOf " } else { "" };
+        write!(f, "expected {:?}, got {}{:?}", self.expected, list, self.actual)
+    }
+}
+
+impl std::error::Error for TypeMismatch {}
+
+/// Error returned when a string is not a valid RFC 3339 date-time, holds the input string
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidDateTime(pub String);
+
+impl fmt::Display for InvalidDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid RFC 3339 date-time: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidDateTime {}
+
+// parse ASCII decimal digits at the given position
+fn parse_digits(s: &str, range: Range<usize>) -> Option<u16> {
+    s.get(range)
+        .filter(|d| d.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|d| d.parse().ok())
+}
+
+// language and string of a text or name with language: each part has its own length prefix
+fn read_with_language(reader: &mut dyn Read, vsize: usize) -> io::Result<(String, String)> {
+    let data = reader.read_bytes(vsize)?;
+    let mut cursor = io::Cursor::new(&data[..]);
+    let mut read_part = || -> io::Result<String> {
+        let len = cursor.read_u16::<BigEndian>()? as usize;
+        cursor.read_string(len)
+    };
+    match (read_part(), read_part()) {
+        (Ok(lang), Ok(text)) if cursor.position() as usize == data.len() => Ok((lang, text)),
+        // the value itself is complete, so running out of data inside of it is a malformed value
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Invalid length of value with language",
+        )),
+    }
+}
+
 impl IppValue {
+    /// Create collection value from (member name, member value) pairs.
+    /// Member names are embedded as `MemberAttrName` values preceding each member
+    pub fn collection<I, N>(members: I) -> IppValue
+    where
+        I: IntoIterator<Item = (N, IppValue)>,
+        N: Into<String>,
+    {
+        IppValue::Collection(
+            members
+                .into_iter()
+                .flat_map(|(name, value)| vec![IppValue::MemberAttrName(name.into()), value])
+                .collect(),
+        )
+    }
+
+    /// Value of the named member of a collection, for example `x-dimension` of a `media-size`.
+    /// Returns None if the member is absent or if this is not a collection
+    pub fn collection_member(&self, name: &str) -> Option<&IppValue> {
+        match *self {
+            IppValue::Collection(ref list) => list
+                .windows(2)
+                .find(|pair| pair[0].as_memberattrname().map(String::as_str) == Some(name))
+                .map(|pair| &pair[1])
+                .filter(|value| value.as_memberattrname().is_none()),
+            _ => None,
+        }
+    }
+
+    /// Create `DateTime` value from an RFC 3339 string such as `2024-01-15T13:45:30.5+02:00`.
+    /// Fractional seconds are truncated to deciseconds, `Z` is stored as `+00:00`
+    pub fn datetime_from_rfc3339(s: &str) -> Result<IppValue, InvalidDateTime> {
+        IppValue::parse_rfc3339(s).ok_or_else(|| InvalidDateTime(s.to_owned()))
+    }
+
+    fn parse_rfc3339(s: &str) -> Option<IppValue> {
+        let b = s.as_bytes();
+        if b.len() < 20 || b[4] != b'-' || b[7] != b'-' || b[13] != b':' || b[16] != b':' {
+            return None;
+        }
+        if b[10] != b'T' && b[10] != b't' {
+            return None;
+        }
+        let year = parse_digits(s, 0..4)?;
+        let month = parse_digits(s, 5..7)? as u8;
+        let day = parse_digits(s, 8..10)? as u8;
+        let hour = parse_digits(s, 11..13)? as u8;
+        let minutes = parse_digits(s, 14..16)? as u8;
+        let seconds = parse_digits(s, 17..19)? as u8;
+
+        let mut rest = &s[19..];
+        let mut deciseconds = 0;
+        if let Some(fraction) = rest.strip_prefix('.') {
+            let len = fraction.bytes().take_while(u8::is_ascii_digit).count();
+            if len == 0 {
+                return None;
+            }
+            deciseconds = fraction.as_bytes()[0] - b'0';
+            rest = &fraction[len..];
+        }
+
+        let (utcdir, utchours, utcmins) = match rest {
+            "Z" | "z" => ('+', 0, 0),
+            _ => {
+                if rest.len() != 6 || rest.as_bytes()[3] != b':' {
+                    return None;
+                }
+                let utcdir = match rest.as_bytes()[0] {
+                    b'+' => '+',
+                    b'-' => '-',
+                    _ => return None,
+                };
+                (utcdir, parse_digits(rest, 1..3)? as u8, parse_digits(rest, 4..6)? as u8)
+            }
+        };
+
+        if month == 0 || month > 12 || day == 0 || day > 31 || hour > 23 || minutes > 59 || seconds > 60 {
+            return None;
+        }
+        if utchours > 23 || utcmins > 59 {
+            return None;
+        }
+
+        Some(IppValue::DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minutes,
+            seconds,
+            deciseconds,
+            utcdir,
+            utchours,
+            utcmins,
+        })
+    }
+
+    /// Format `DateTime` value as an RFC 3339 string, deciseconds are included when not zero.
+    /// Returns None for other values or if the deciseconds or UTC direction are invalid
+    pub fn to_rfc3339(&self) -> Option<String> {
+        match *self {
+            IppValue::DateTime {
+                year,
+                month,
+                day,
+                hour,
+                minutes,
+                seconds,
+                deciseconds,
+                utcdir,
+                utchours,
+                utcmins,
+            } => {
+                if deciseconds > 9 || (utcdir != '+' && utcdir != '-') {
+                    return None;
+                }
+                let fraction = if deciseconds > 0 {
+                    format!(".{}", deciseconds)
+                } else {
+                    String::new()
+                };
+                Some(format!(
+                    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}{}{:02}:{:02}",
+                    year, month, day, hour, minutes, seconds, fraction, utcdir, utchours, utcmins
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Convert `DateTime` value to a timestamp with the UTC offset of the value.
+    /// Returns None for other values or if any of the fields is out of range
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        use chrono::{FixedOffset, NaiveDate, TimeZone};
+
+        match *self {
+            IppValue::DateTime {
+                year,
+                month,
+                day,
+                hour,
+                minutes,
+                seconds,
+                deciseconds,
+                utcdir,
+                utchours,
+                utcmins,
+            } => {
+                if deciseconds > 9 || utcmins > 59 {
+                    return None;
+                }
+                let offset = (i32::from(utchours) * 60 + i32::from(utcmins)) * 60;
+                let offset = match utcdir {
+                    '+' => FixedOffset::east_opt(offset)?,
+                    '-' => FixedOffset::west_opt(offset)?,
+                    _ => return None,
+                };
+                let local = NaiveDate::from_ymd_opt(i32::from(year), u32::from(month), u32::from(day))?
+                    .and_hms_milli_opt(
+                        u32::from(hour),
+                        u32::from(minutes),
+                        u32::from(seconds),
+                        u32::from(deciseconds) * 100,
+                    )?;
+                offset.from_local_datetime(&local).single()
+            }
+            _ => None,
+        }
+    }
+
+    /// Create `sides` keyword value
+    pub fn sides(sides: Sides) -> IppValue {
+        IppValue::Keyword(sides.as_keyword().to_owned())
+    }
+
+    /// Create `print-quality` enum value
+    pub fn print_quality(quality: PrintQuality) -> IppValue {
+        IppValue::Enum(quality as i32)
+    }
+
+    /// Create `orientation-requested` enum value
+    pub fn orientation_requested(orientation: Orientation) -> IppValue {
+        IppValue::Enum(orientation as i32)
+    }
+
+    /// Create `media` keyword value from a PWG 5101.1 self-describing media name, for example
+    /// `iso_a4_210x297mm`. Returns None if the name does not describe the media dimensions
+    pub fn media(name: &str) -> Option<IppValue> {
+        if name.split('_').count() < 3 {
+            return None;
+        }
+        MediaSize::from_pwg_name(name).map(|_| IppValue::Keyword(name.to_owned()))
+    }
+
     /// Convert to binary tag
     pub fn to_tag(&self) -> ValueTag {
         match *self {
@@ -68,6 +364,8 @@ impl IppValue {
             IppValue::Keyword(_) => ValueTag::Keyword,
             IppValue::OctetString(_) => ValueTag::OctetStringUnspecified,
             IppValue::TextWithoutLanguage(_) => ValueTag::TextWithoutLanguage,
+            IppValue::TextWithLanguage { .. } => ValueTag::TextWithLanguage,
+            IppValue::NameWithLanguage { .. } => ValueTag::NameWithLanguage,
             IppValue::NameWithoutLanguage(_) => ValueTag::NameWithoutLanguage,
             IppValue::Charset(_) => ValueTag::Charset,
             IppValue::NaturalLanguage(_) => ValueTag::NaturalLanguage,
@@ -78,10 +376,146 @@ impl IppValue {
             IppValue::DateTime { .. } => ValueTag::DateTime,
             IppValue::MemberAttrName(_) => ValueTag::MemberAttrName,
             IppValue::Resolution { .. } => ValueTag::Resolution,
+            IppValue::Unsupported => ValueTag::Unsupported,
+            IppValue::Unknown => ValueTag::Unknown,
+            IppValue::NoValue => ValueTag::NoValue,
             IppValue::Other { .. } => ValueTag::Unknown,
         }
     }
 
+    /// Format value together with its IPP syntax, e.g. `integer(42)` or `keyword("one-sided")`.
+    /// Collections list their members with member names where present
+    pub fn describe(&self) -> String {
+        match *self {
+            IppValue::Integer(i) => format!("integer({})", i),
+            IppValue::Enum(i) => format!("enum({})", i),
+            IppValue::RangeOfInteger { min, max } => format!("rangeOfInteger({}..{})", min, max),
+            IppValue::Boolean(b) => format!("boolean({})", b),
+            IppValue::Keyword(ref s) => format!("keyword({:?})", s),
+            IppValue::OctetString(ref s) => format!("octetString({:?})", s),
+            IppValue::TextWithoutLanguage(ref s) => format!("textWithoutLanguage({:?})", s),
+            IppValue::TextWithLanguage { ref lang, ref text } => format!("textWithLanguage({}, {:?})", lang, text),
+            IppValue::NameWithLanguage { ref lang, ref name } => format!("nameWithLanguage({}, {:?})", lang, name),
+            IppValue::NameWithoutLanguage(ref s) => format!("nameWithoutLanguage({:?})", s),
+            IppValue::Charset(ref s) => format!("charset({:?})", s),
+            IppValue::NaturalLanguage(ref s) => format!("naturalLanguage({:?})", s),
+            IppValue::Uri(ref s) => format!("uri({:?})", s),
+            IppValue::MimeMediaType(ref s) => format!("mimeMediaType({:?})", s),
+            IppValue::MemberAttrName(ref s) => format!("memberAttrName({:?})", s),
+            IppValue::DateTime { .. } => format!("dateTime({})", self),
+            IppValue::Resolution { .. } => format!("resolution({})", self),
+            IppValue::Unsupported | IppValue::Unknown | IppValue::NoValue => self.to_string(),
+            IppValue::ListOf(ref list) => {
+                let s: Vec<String> = list.iter().map(IppValue::describe).collect();
+                format!("1setOf[{}]", s.join(", "))
+            }
+            IppValue::Collection(ref list) => {
+                let mut members = Vec::new();
+                let mut iter = list.iter();
+                while let Some(value) = iter.next() {
+                    match value {
+                        IppValue::MemberAttrName(name) => match iter.next() {
+                            Some(member) => members.push(format!("{}: {}", name, member.describe())),
+                            None => members.push(value.describe()),
+                        },
+                        value => members.push(value.describe()),
+                    }
+                }
+                let plural = if members.len() == 1 { "" } else { "s" };
+                format!(
+                    "collection{{{} member{}: {}}}",
+                    members.len(),
+                    plural,
+                    members.join(", ")
+                )
+            }
+            IppValue::Other { tag, ref data } => format!("unknown(0x{:02x}, {} bytes)", tag, data.len()),
+        }
+    }
+
+    fn mismatch(&self, expected: ValueTag) -> TypeMismatch {
+        TypeMismatch {
+            expected,
+            actual: self.to_tag(),
+            list: self.as_listof().is_some(),
+        }
+    }
+
+    /// Get `Integer` value or a type mismatch error
+    pub fn expect_integer(&self) -> Result<i32, TypeMismatch> {
+        match *self {
+            IppValue::Integer(i) => Ok(i),
+            _ => Err(self.mismatch(ValueTag::Integer)),
+        }
+    }
+
+    /// Get `Enum` value or a type mismatch error
+    pub fn expect_enum(&self) -> Result<i32, TypeMismatch> {
+        match *self {
+            IppValue::Enum(i) => Ok(i),
+            _ => Err(self.mismatch(ValueTag::Enum)),
+        }
+    }
+
+    /// Get `Boolean` value or a type mismatch error
+    pub fn expect_boolean(&self) -> Result<bool, TypeMismatch> {
+        match *self {
+            IppValue::Boolean(b) => Ok(b),
+            _ => Err(self.mismatch(ValueTag::Boolean)),
+        }
+    }
+
+    /// Get `Keyword` value or a type mismatch error
+    pub fn expect_keyword(&self) -> Result<&str, TypeMismatch> {
+        match *self {
+            IppValue::Keyword(ref s) => Ok(s),
+            _ => Err(self.mismatch(ValueTag::Keyword)),
+        }
+    }
+
+    /// Get `Uri` value or a type mismatch error
+    pub fn expect_uri(&self) -> Result<&str, TypeMismatch> {
+        match *self {
+            IppValue::Uri(ref s) => Ok(s),
+            _ => Err(self.mismatch(ValueTag::Uri)),
+        }
+    }
+
+    /// Get text or name value, with or without language, or a type mismatch error
+    pub fn expect_text(&self) -> Result<&str, TypeMismatch> {
+        match *self {
+            IppValue::TextWithoutLanguage(ref s)
+            | IppValue::NameWithoutLanguage(ref s)
+            | IppValue::TextWithLanguage { text: ref s, .. }
+            | IppValue::NameWithLanguage { name: ref s, .. } => Ok(s),
+            _ => Err(self.mismatch(ValueTag::TextWithoutLanguage)),
+        }
+    }
+
+    /// String of any string-valued type, including octet strings and the text and name of
+    /// values with a natural language. Returns None for other values and for lists
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            IppValue::Keyword(ref s)
+            | IppValue::OctetString(ref s)
+            | IppValue::Uri(ref s)
+            | IppValue::MimeMediaType(ref s)
+            | IppValue::Charset(ref s)
+            | IppValue::NaturalLanguage(ref s)
+            | IppValue::TextWithoutLanguage(ref s)
+            | IppValue::NameWithoutLanguage(ref s)
+            | IppValue::TextWithLanguage { text: ref s, .. }
+            | IppValue::NameWithLanguage { name: ref s, .. } => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// String values of a single value or a list, for attributes which may have either shape,
+    /// for example `job-state-reasons`. Values which are not strings are skipped, see `as_str`
+    pub fn as_string_list(&self) -> Vec<&str> {
+        self.into_iter().filter_map(IppValue::as_str).collect()
+    }
+
     /// Read value from binary stream
     pub fn read(vtag: u8, reader: &mut dyn Read) -> io::Result<IppValue> {
         let vsize = reader.read_u16::<BigEndian>()?;
@@ -107,6 +541,14 @@ impl IppValue {
             }
             ValueTag::OctetStringUnspecified => Ok(IppValue::OctetString(reader.read_string(vsize as usize)?)),
             ValueTag::TextWithoutLanguage => Ok(IppValue::TextWithoutLanguage(reader.read_string(vsize as usize)?)),
+            ValueTag::TextWithLanguage => {
+                let (lang, text) = read_with_language(reader, vsize as usize)?;
+                Ok(IppValue::TextWithLanguage { lang, text })
+            }
+            ValueTag::NameWithLanguage => {
+                let (lang, name) = read_with_language(reader, vsize as usize)?;
+                Ok(IppValue::NameWithLanguage { lang, name })
+            }
             ValueTag::NameWithoutLanguage => Ok(IppValue::NameWithoutLanguage(reader.read_string(vsize as usize)?)),
             ValueTag::Charset => Ok(IppValue::Charset(reader.read_string(vsize as usize)?)),
             ValueTag::NaturalLanguage => Ok(IppValue::NaturalLanguage(reader.read_string(vsize as usize)?)),
@@ -142,6 +584,10 @@ impl IppValue {
                 feed: reader.read_i32::<BigEndian>()?,
                 units: reader.read_i8()?,
             }),
+            // out-of-band values carry no data, anything present is skipped
+            ValueTag::Unsupported => reader.read_bytes(vsize as usize).map(|_| IppValue::Unsupported),
+            ValueTag::Unknown => reader.read_bytes(vsize as usize).map(|_| IppValue::Unknown),
+            ValueTag::NoValue => reader.read_bytes(vsize as usize).map(|_| IppValue::NoValue),
             _ => Ok(IppValue::Other {
                 tag: vtag,
                 data: reader.read_bytes(vsize as usize)?,
@@ -150,8 +596,22 @@ impl IppValue {
     }
 }
 
+// name and value lengths are 16-bit fields, longer strings cannot be encoded
+pub(crate) fn write_len(writer: &mut dyn Write, len: usize) -> io::Result<()> {
+    if len > u16::MAX as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("IPP name or value length {} exceeds maximum of {} bytes", len, u16::MAX),
+        ));
+    }
+    writer.write_u16::<BigEndian>(len as u16)
+}
+
 impl IppWriter for IppValue {
-    /// Write value to binary stream
+    /// Write value to binary stream.
+    ///
+    /// Each value of a `ListOf` is encoded separately, so the 65535 byte limit applies per element.
+    /// A longer element fails with `InvalidInput` error instead of being truncated
     fn write(&self, writer: &mut dyn Write) -> io::Result<usize> {
         match *self {
             IppValue::Integer(i) | IppValue::Enum(i) => {
@@ -179,10 +639,20 @@ impl IppWriter for IppValue {
             | IppValue::Uri(ref s)
             | IppValue::MimeMediaType(ref s)
             | IppValue::MemberAttrName(ref s) => {
-                writer.write_u16::<BigEndian>(s.len() as u16)?;
+                write_len(writer, s.len())?;
                 writer.write_all(s.as_bytes())?;
                 Ok(2 + s.len())
             }
+            IppValue::TextWithLanguage { ref lang, text: ref s }
+            | IppValue::NameWithLanguage { ref lang, name: ref s } => {
+                let len = 4 + lang.len() + s.len();
+                write_len(writer, len)?;
+                write_len(writer, lang.len())?;
+                writer.write_all(lang.as_bytes())?;
+                write_len(writer, s.len())?;
+                writer.write_all(s.as_bytes())?;
+                Ok(2 + len)
+            }
             IppValue::ListOf(ref list) => {
                 let mut retval = 0;
                 for (i, item) in list.iter().enumerate() {
@@ -249,10 +719,14 @@ impl IppWriter for IppValue {
                 writer.write_i32::<BigEndian>(crossfeed)?;
                 writer.write_i32::<BigEndian>(feed)?;
                 writer.write_i8(units)?;
-                Ok(9)
+                Ok(11)
+            }
+            IppValue::Unsupported | IppValue::Unknown | IppValue::NoValue => {
+                writer.write_u16::<BigEndian>(0)?;
+                Ok(2)
             }
             IppValue::Other { ref data, .. } => {
-                writer.write_u16::<BigEndian>(data.len() as u16)?;
+                write_len(writer, data.len())?;
                 writer.write_all(data)?;
                 Ok(2 + data.len())
             }
@@ -276,6 +750,8 @@ impl fmt::Display for IppValue {
             | IppValue::Uri(ref s)
             | IppValue::MimeMediaType(ref s)
             | IppValue::MemberAttrName(ref s) => write!(f, "{}", s),
+            IppValue::TextWithLanguage { ref text, .. } => write!(f, "{}", text),
+            IppValue::NameWithLanguage { ref name, .. } => write!(f, "{}", name),
             IppValue::ListOf(ref list) => {
                 let s: Vec<String> = list.iter().map(|v| format!("{}", v)).collect();
                 write!(f, "[{}]", s.join(", "))
@@ -298,11 +774,16 @@ impl fmt::Display for IppValue {
             } => write!(
                 f,
                 "{}-{}-{},{}:{}:{}.{},{}{}utc",
-                year, month, day, hour, minutes, seconds, deciseconds, utcdir as char, utchours
+                year, month, day, hour, minutes, seconds, deciseconds, utcdir, utchours
             ),
-            IppValue::Resolution { crossfeed, feed, units } => {
-                write!(f, "{}x{}{}", crossfeed, feed, if units == 3 { "in" } else { "cm" })
-            }
+            IppValue::Resolution { crossfeed, feed, units } => match units {
+                3 => write!(f, "{}x{}dpi", crossfeed, feed),
+                4 => write!(f, "{}x{}dpcm", crossfeed, feed),
+                _ => write!(f, "{}x{} (unit {})", crossfeed, feed, units),
+            },
+            IppValue::Unsupported => write!(f, "unsupported"),
+            IppValue::Unknown => write!(f, "unknown"),
+            IppValue::NoValue => write!(f, "no-value"),
 
             IppValue::Other { tag, ref data } => write!(f, "{:0x}: {:?}", tag, data),
         }
@@ -405,4 +886,436 @@ mod tests {
             Some(&vec![IppValue::Integer(0x11111111), IppValue::Integer(0x22222222)])
         );
     }
+
+    #[test]
+    fn test_list_element_length_limit() {
+        let list = |len: usize| {
+            IppValue::ListOf(vec![
+                IppValue::Keyword("short".to_owned()),
+                IppValue::TextWithoutLanguage("x".repeat(len)),
+            ])
+        };
+
+        let mut buf = Vec::new();
+        assert_eq!(list(65535).write(&mut buf).unwrap(), buf.len());
+
+        let result = list(65536).write(&mut Vec::new());
+        assert_eq!(result.map_err(|e| e.kind()).err(), Some(io::ErrorKind::InvalidInput));
+    }
+
+    #[test]
+    fn test_collection_member() {
+        let media_col = IppValue::collection(vec![
+            (
+                "media-size",
+                IppValue::collection(vec![
+                    ("x-dimension", IppValue::Integer(21000)),
+                    ("y-dimension", IppValue::Integer(29700)),
+                ]),
+            ),
+            ("media-source", IppValue::Keyword("main".to_owned())),
+        ]);
+
+        let size = media_col.collection_member("media-size").unwrap();
+        assert_eq!(size.collection_member("x-dimension"), Some(&IppValue::Integer(21000)));
+        assert_eq!(size.collection_member("y-dimension"), Some(&IppValue::Integer(29700)));
+        assert_eq!(
+            media_col.collection_member("media-source"),
+            Some(&IppValue::Keyword("main".to_owned()))
+        );
+        assert_eq!(media_col.collection_member("x-dimension"), None);
+        assert_eq!(IppValue::Integer(1).collection_member("media-size"), None);
+    }
+
+    #[test]
+    fn test_value_with_language() {
+        let value = IppValue::TextWithLanguage {
+            lang: "de".to_owned(),
+            text: "Drucker im Büro".to_owned(),
+        };
+        let mut buf = Vec::new();
+        assert_eq!(value.write(&mut buf).unwrap(), buf.len());
+        let text = "Drucker im Büro".as_bytes();
+        let mut expected = vec![0, (6 + text.len()) as u8, 0, 2, b'd', b'e', 0, text.len() as u8];
+        expected.extend_from_slice(text);
+        assert_eq!(buf, expected);
+        assert_eq!(value.to_tag(), ValueTag::TextWithLanguage);
+        assert_eq!(
+            IppValue::read(ValueTag::TextWithLanguage as u8, &mut io::Cursor::new(&buf)).unwrap(),
+            value
+        );
+        assert_eq!(value.to_string(), "Drucker im Büro");
+        assert_eq!(value.expect_text(), Ok("Drucker im Büro"));
+
+        let value = IppValue::NameWithLanguage {
+            lang: "de".to_owned(),
+            name: "Bericht".to_owned(),
+        };
+        let attr = IppAttribute::new("job-name", value.clone());
+        let mut buf = Vec::new();
+        attr.write(&mut buf).unwrap();
+        assert_eq!(buf[0], ValueTag::NameWithLanguage as u8);
+        assert_eq!(IppValue::read(buf[0], &mut io::Cursor::new(&buf[11..])).unwrap(), value);
+        assert_eq!(value.describe(), "nameWithLanguage(de, \"Bericht\")");
+
+        // inner lengths inconsistent with the value length
+        let data = [0, 6, 0, 2, b'd', b'e', 0, 1, b'x'];
+        let result = IppValue::read(ValueTag::TextWithLanguage as u8, &mut io::Cursor::new(&data[..]));
+        assert_eq!(result.map_err(|e| e.kind()).err(), Some(io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn test_expect_value() {
+        assert_eq!(IppValue::Integer(5).expect_integer(), Ok(5));
+        assert_eq!(IppValue::Enum(3).expect_enum(), Ok(3));
+        assert_eq!(IppValue::Boolean(true).expect_boolean(), Ok(true));
+        assert_eq!(IppValue::Keyword("none".to_owned()).expect_keyword(), Ok("none"));
+        assert_eq!(IppValue::NameWithoutLanguage("job".to_owned()).expect_text(), Ok("job"));
+
+        let err = IppValue::Keyword("none".to_owned()).expect_integer().unwrap_err();
+        assert_eq!(err.expected, ValueTag::Integer);
+        assert_eq!(err.actual, ValueTag::Keyword);
+        assert_eq!(err.to_string(), "expected Integer, got Keyword");
+
+        let err = IppValue::ListOf(vec![IppValue::Uri("ipp://printer".to_owned())])
+            .expect_uri()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "expected Uri, got 1setOf Uri");
+    }
+
+    #[test]
+    fn test_oversized_value() {
+        let mut buf = Vec::new();
+        let result = IppValue::Keyword("k".repeat(70000)).write(&mut buf);
+        assert_eq!(result.map_err(|e| e.kind()).err(), Some(io::ErrorKind::InvalidInput));
+        assert!(buf.is_empty());
+
+        let value = IppValue::Other {
+            tag: 0x7f,
+            data: Bytes::from(vec![0u8; 65536]),
+        };
+        let result = value.write(&mut Vec::new());
+        assert_eq!(result.map_err(|e| e.kind()).err(), Some(io::ErrorKind::InvalidInput));
+
+        let attr = IppAttribute::new(&"n".repeat(65536), IppValue::Integer(1));
+        let result = attr.write(&mut Vec::new());
+        assert_eq!(result.map_err(|e| e.kind()).err(), Some(io::ErrorKind::InvalidInput));
+    }
+
+    #[test]
+    fn test_written_length() {
+        let value = IppValue::Resolution {
+            crossfeed: 600,
+            feed: 600,
+            units: 3,
+        };
+        let mut buf = Vec::new();
+        assert_eq!(value.write(&mut buf).unwrap(), buf.len());
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(IppValue::Integer(42).describe(), "integer(42)");
+        assert_eq!(
+            IppValue::Keyword("two-sided-long-edge".to_owned()).describe(),
+            "keyword(\"two-sided-long-edge\")"
+        );
+        assert_eq!(
+            IppValue::RangeOfInteger { min: 1, max: 999 }.describe(),
+            "rangeOfInteger(1..999)"
+        );
+        assert_eq!(
+            IppValue::ListOf(vec![IppValue::Integer(1), IppValue::Enum(3)]).describe(),
+            "1setOf[integer(1), enum(3)]"
+        );
+
+        let value = IppValue::collection(vec![
+            (
+                "media-size",
+                IppValue::collection(vec![("x-dimension", IppValue::Integer(21000))]),
+            ),
+            ("media-source", IppValue::Keyword("main".to_owned())),
+        ]);
+        assert_eq!(
+            value.describe(),
+            "collection{2 members: media-size: collection{1 member: x-dimension: integer(21000)}, \
+             media-source: keyword(\"main\")}"
+        );
+    }
+
+    #[test]
+    fn test_collection_builder_round_trip() {
+        let value = IppValue::collection(vec![
+            ("media-size-name", IppValue::Keyword("iso_a4_210x297mm".to_owned())),
+            ("media-source", IppValue::Keyword("main".to_owned())),
+        ]);
+        assert_eq!(
+            value,
+            IppValue::Collection(vec![
+                IppValue::MemberAttrName("media-size-name".to_owned()),
+                IppValue::Keyword("iso_a4_210x297mm".to_owned()),
+                IppValue::MemberAttrName("media-source".to_owned()),
+                IppValue::Keyword("main".to_owned()),
+            ])
+        );
+
+        let mut buf = Vec::new();
+        assert!(IppAttribute::new("media-col", value.clone())
+            .write(&mut io::Cursor::new(&mut buf))
+            .is_ok());
+
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        data.extend(buf);
+        data.push(3);
+
+        let res = crate::parser::IppParser::new(&mut io::Cursor::new(data))
+            .parse()
+            .unwrap();
+        let attrs = res.attributes.groups_of(DelimiterTag::PrinterAttributes)[0].attributes();
+        assert_eq!(attrs.get("media-col").map(|a| a.value()), Some(&value));
+    }
+
+    #[test]
+    fn test_nested_collection_member_order() {
+        let value = IppValue::collection(vec![
+            (
+                "media-size",
+                IppValue::collection(vec![
+                    ("x-dimension", IppValue::Integer(21000)),
+                    ("y-dimension", IppValue::Integer(29700)),
+                ]),
+            ),
+            ("media-type", IppValue::Keyword("stationery".to_owned())),
+            ("media-source", IppValue::Keyword("main".to_owned())),
+        ]);
+
+        let mut buf = Vec::new();
+        assert!(IppAttribute::new("media-col", value.clone())
+            .write(&mut io::Cursor::new(&mut buf))
+            .is_ok());
+
+        // member names in the order of appearance in the serialized stream
+        let mut names = Vec::new();
+        let mut cursor = io::Cursor::new(&buf[..]);
+        while let Ok(tag) = cursor.read_u8() {
+            let namelen = cursor.read_u16::<BigEndian>().unwrap();
+            cursor.set_position(cursor.position() + u64::from(namelen));
+            let value = IppValue::read(tag, &mut cursor).unwrap();
+            if let IppValue::MemberAttrName(name) = value {
+                names.push(name);
+            }
+        }
+        assert_eq!(
+            names,
+            vec!["media-size", "x-dimension", "y-dimension", "media-type", "media-source"]
+        );
+
+        let mut data = vec![1, 1, 0, 0, 0, 0, 0, 0, 4];
+        data.extend(buf.clone());
+        data.push(3);
+
+        let res = crate::parser::IppParser::new(&mut io::Cursor::new(data))
+            .parse()
+            .unwrap();
+        let attrs = res.attributes.groups_of(DelimiterTag::PrinterAttributes)[0].attributes();
+        let parsed = attrs.get("media-col").unwrap();
+        assert_eq!(parsed.value(), &value);
+
+        let mut reserialized = Vec::new();
+        assert!(parsed.write(&mut io::Cursor::new(&mut reserialized)).is_ok());
+        assert_eq!(reserialized, buf);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let values = vec![
+            IppValue::Integer(42),
+            IppValue::Keyword("one-sided".to_owned()),
+            IppValue::RangeOfInteger { min: 1, max: 999 },
+            IppValue::DateTime {
+                year: 2019,
+                month: 5,
+                day: 1,
+                hour: 12,
+                minutes: 30,
+                seconds: 15,
+                deciseconds: 0,
+                utcdir: '+',
+                utchours: 2,
+                utcmins: 0,
+            },
+            IppValue::Resolution {
+                crossfeed: 600,
+                feed: 600,
+                units: 3,
+            },
+            IppValue::Other {
+                tag: 0x7f,
+                data: Bytes::from_static(b"\x01\x02\xff"),
+            },
+            IppValue::ListOf(vec![IppValue::Boolean(true), IppValue::Enum(3)]),
+            IppValue::collection(vec![(
+                "media-size-name",
+                IppValue::Keyword("iso_a4_210x297mm".to_owned()),
+            )]),
+        ];
+
+        for value in values {
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(serde_json::from_str::<IppValue>(&json).unwrap(), value);
+        }
+
+        assert_eq!(
+            serde_json::to_value(IppValue::Integer(42)).unwrap(),
+            serde_json::json!({"type": "integer", "value": 42})
+        );
+        assert_eq!(
+            serde_json::to_value(IppValue::Other {
+                tag: 0x7f,
+                data: Bytes::from_static(b"ipp"),
+            })
+            .unwrap(),
+            serde_json::json!({"type": "other", "value": {"tag": 127, "data": "aXBw"}})
+        );
+    }
+
+    #[test]
+    fn test_validated_constructors() {
+        assert_eq!(
+            IppValue::sides(Sides::TwoSidedLongEdge),
+            IppValue::Keyword("two-sided-long-edge".to_owned())
+        );
+        assert_eq!(IppValue::print_quality(PrintQuality::High), IppValue::Enum(5));
+        assert_eq!(
+            IppValue::orientation_requested(Orientation::Landscape),
+            IppValue::Enum(4)
+        );
+        assert_eq!(
+            IppValue::media("na_letter_8.5x11in"),
+            Some(IppValue::Keyword("na_letter_8.5x11in".to_owned()))
+        );
+        assert_eq!(IppValue::media("iso-a4"), None);
+        assert_eq!(IppValue::media("210x297mm"), None);
+    }
+
+    #[test]
+    fn test_resolution_display() {
+        let resolution = |units| IppValue::Resolution {
+            crossfeed: 600,
+            feed: 300,
+            units,
+        };
+        assert_eq!(resolution(3).to_string(), "600x300dpi");
+        assert_eq!(resolution(4).to_string(), "600x300dpcm");
+        assert_eq!(resolution(5).to_string(), "600x300 (unit 5)");
+    }
+
+    #[test]
+    fn test_as_string_list() {
+        assert_eq!(IppValue::Keyword("none".to_owned()).as_string_list(), vec!["none"]);
+        let list = IppValue::ListOf(vec![
+            IppValue::Keyword("job-incoming".to_owned()),
+            IppValue::NameWithoutLanguage("job-printing".to_owned()),
+            IppValue::Integer(5),
+        ]);
+        assert_eq!(list.as_string_list(), vec!["job-incoming", "job-printing"]);
+        assert!(IppValue::Integer(5).as_string_list().is_empty());
+
+        assert_eq!(IppValue::OctetString("0x1f".to_owned()).as_str(), Some("0x1f"));
+        assert_eq!(list.as_str(), None);
+    }
+
+    #[test]
+    fn test_out_of_band_values() {
+        for (value, tag) in [
+            (IppValue::Unsupported, 0x10),
+            (IppValue::Unknown, 0x12),
+            (IppValue::NoValue, 0x13),
+        ] {
+            let attr = IppAttribute::new("sides", value.clone());
+            let mut buf = Vec::new();
+            assert_eq!(attr.write(&mut buf).unwrap(), 10);
+            assert_eq!(buf, vec![tag, 0, 5, b's', b'i', b'd', b'e', b's', 0, 0]);
+
+            assert_eq!(IppValue::read(tag, &mut io::Cursor::new(&buf[8..])).unwrap(), value);
+        }
+        assert_eq!(IppValue::NoValue.to_string(), "no-value");
+    }
+
+    #[test]
+    fn test_rfc3339() {
+        for s in &[
+            "2024-01-15T13:45:30+02:00",
+            "2024-01-15T13:45:30.5-05:30",
+            "1999-12-31T23:59:60+00:00",
+        ] {
+            let value = IppValue::datetime_from_rfc3339(s).unwrap();
+            assert_eq!(value.to_rfc3339().as_deref(), Some(*s));
+        }
+
+        let value = IppValue::datetime_from_rfc3339("2024-01-15T13:45:30.567-08:00").unwrap();
+        assert_eq!(
+            value,
+            IppValue::DateTime {
+                year: 2024,
+                month: 1,
+                day: 15,
+                hour: 13,
+                minutes: 45,
+                seconds: 30,
+                deciseconds: 5,
+                utcdir: '-',
+                utchours: 8,
+                utcmins: 0,
+            }
+        );
+        assert_eq!(
+            IppValue::datetime_from_rfc3339("2024-01-15T13:45:30Z")
+                .unwrap()
+                .to_rfc3339()
+                .unwrap(),
+            "2024-01-15T13:45:30+00:00"
+        );
+
+        for s in &[
+            "",
+            "2024-01-15 13:45:30Z",
+            "2024-13-15T13:45:30Z",
+            "2024-01-15T13:45:30.Z",
+            "2024-01-15T13:45:30+0200",
+        ] {
+            assert_eq!(IppValue::datetime_from_rfc3339(s), Err(InvalidDateTime(s.to_string())));
+        }
+        assert!(IppValue::Integer(1).to_rfc3339().is_none());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_to_chrono() {
+        let datetime = |utcdir, utchours, utcmins| IppValue::DateTime {
+            year: 2019,
+            month: 5,
+            day: 1,
+            hour: 12,
+            minutes: 30,
+            seconds: 15,
+            deciseconds: 5,
+            utcdir,
+            utchours,
+            utcmins,
+        };
+
+        let east = datetime('+', 2, 0).to_chrono().unwrap();
+        assert_eq!(east.to_rfc3339(), "2019-05-01T12:30:15.500+02:00");
+        assert_eq!(east.naive_utc().to_string(), "2019-05-01 10:30:15.500");
+
+        let west = datetime('-', 5, 30).to_chrono().unwrap();
+        assert_eq!(west.to_rfc3339(), "2019-05-01T12:30:15.500-05:30");
+        assert_eq!(west.naive_utc().to_string(), "2019-05-01 18:00:15.500");
+
+        assert!(datetime('x', 0, 0).to_chrono().is_none());
+        assert!(datetime('+', 0, 60).to_chrono().is_none());
+        assert!(IppValue::Integer(1).to_chrono().is_none());
+    }
 }
@@ -177,7 +177,7 @@ impl IppRequestHandler for TestServer {
     }
 
     fn get_printer_attributes(&self, req: IppRequestResponse) -> IppServerResult {
-        static SUPPORTED_ATTRIBUTES: &[&'static str] = &[
+        static SUPPORTED_ATTRIBUTES: &[&str] = &[
             PRINTER_URI_SUPPORTED,
             URI_SECURITY_SUPPORTED,
             URI_AUTHENTICATION_SUPPORTED,
@@ -209,7 +209,7 @@ impl IppRequestHandler for TestServer {
         let requested_attributes = req
             .attributes()
             .groups_of(DelimiterTag::OperationAttributes)
-            .get(0)
+            .first()
             .and_then(|g| g.attributes().get(REQUESTED_ATTRIBUTES))
             .map(|attr| {
                 attr.value()
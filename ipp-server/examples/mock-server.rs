@@ -0,0 +1,44 @@
+//!
+//! Query a `MockServer` with the IPP client, the way a client test exercises `IppClient::send`
+//! end-to-end without a real printer
+//!
+use std::error::Error;
+
+use ipp_client::IppClientBuilder;
+use ipp_proto::{
+    attribute::{IppAttribute, PRINTER_NAME, PRINTER_STATE},
+    ipp::{DelimiterTag, PrinterState, StatusCode},
+    IppAttributes, IppOperationBuilder, IppValue,
+};
+use ipp_server::testing::MockServer;
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    env_logger::init();
+
+    let mut attributes = IppAttributes::new();
+    attributes.add(
+        DelimiterTag::PrinterAttributes,
+        IppAttribute::new(PRINTER_NAME, IppValue::NameWithoutLanguage("mock".to_owned())),
+    );
+    attributes.add(
+        DelimiterTag::PrinterAttributes,
+        IppAttribute::new(PRINTER_STATE, IppValue::Enum(PrinterState::Idle as i32)),
+    );
+    let server = MockServer::start(StatusCode::SuccessfulOK, attributes)
+        .map_err(|e| format!("Cannot start mock server: {:?}", e))?;
+
+    let mut runtime = tokio::runtime::Runtime::new()?;
+    let client = IppClientBuilder::new(&server.uri()).build();
+    let attrs = runtime.block_on(client.send(IppOperationBuilder::get_printer_attributes().build()))?;
+    assert_eq!(attrs.printer_state(), Some(PrinterState::Idle));
+
+    for v in attrs.groups_of(DelimiterTag::PrinterAttributes)[0]
+        .attributes()
+        .values()
+    {
+        println!("{}: {}", v.name(), v.value());
+    }
+    println!("{} request(s) received by {}", server.requests().len(), server.uri());
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use std::{io, net::SocketAddr, sync::Arc};
 
 use futures::future::IntoFuture;
 use futures::{Future, Poll, Stream};
-use hyper::{service::service_fn, Body, Chunk, Request, Response, Server};
+use hyper::{server::conn::AddrIncoming, service::service_fn, Body, Chunk, Request, Response, Server};
 use log::debug;
 
 use ipp_proto::{
@@ -36,18 +36,20 @@ impl From<io::Error> for ServerError {
 /// IPP server
 pub struct IppServer {
     inner: Box<dyn Future<Item = (), Error = ServerError> + Send>,
+    local_addr: SocketAddr,
 }
 
 impl IppServer {
     fn new(address: SocketAddr, handler: Arc<dyn IppRequestHandler + Send + Sync>) -> Result<IppServer, ServerError> {
-        let inner = Server::try_bind(&address)?
+        let incoming = AddrIncoming::bind(&address)?;
+        let local_addr = incoming.local_addr();
+
+        let inner = Server::builder(incoming)
             .serve(move || {
                 let handler = handler.clone();
                 service_fn(move |req: Request<Body>| {
-                    let stream: Box<dyn Stream<Item = Chunk, Error = io::Error> + Send> = Box::new(
-                        req.into_body()
-                            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string())),
-                    );
+                    let stream: Box<dyn Stream<Item = Chunk, Error = io::Error> + Send> =
+                        Box::new(req.into_body().map_err(|e| io::Error::other(e.to_string())));
 
                     let handler = handler.clone();
 
@@ -77,7 +79,15 @@ impl IppServer {
             })
             .map_err(ServerError::from);
 
-        Ok(IppServer { inner: Box::new(inner) })
+        Ok(IppServer {
+            inner: Box::new(inner),
+            local_addr,
+        })
+    }
+
+    /// Address the server is listening on, useful when binding to port 0
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
     }
 }
 
@@ -0,0 +1,167 @@
+//!
+//! In-process IPP server with canned responses, for client tests
+//!
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use futures::{sync::oneshot, Future};
+
+use ipp_proto::{
+    ipp::{DelimiterTag, StatusCode},
+    request::IppRequestResponse,
+    IppAttributes, IppHeader,
+};
+
+use crate::{
+    handler::{IppRequestHandler, IppServerResult},
+    server::{IppServer, IppServerBuilder, ServerError},
+};
+
+struct CannedHandler {
+    status: StatusCode,
+    attributes: IppAttributes,
+    requests: Mutex<Vec<IppHeader>>,
+}
+
+impl IppRequestHandler for CannedHandler {
+    fn handle_request(&self, req: IppRequestResponse) -> IppServerResult {
+        let header = req.header().clone();
+        self.requests.lock().unwrap().push(header.clone());
+
+        let mut response = IppRequestResponse::new_response(self.version(), self.status, header.request_id);
+        for group in self.attributes.groups() {
+            if group.tag() == DelimiterTag::OperationAttributes {
+                for attribute in group.attributes().values() {
+                    response.attributes_mut().add(group.tag(), attribute.clone());
+                }
+            } else {
+                // several groups of the same kind, for example jobs, are kept apart
+                response.begin_group(group.tag());
+                for attribute in group.attributes().values() {
+                    response.add(attribute.clone());
+                }
+            }
+        }
+        Ok(response)
+    }
+}
+
+/// IPP server running on a background thread which answers every request with the same
+/// status code and attribute groups. The server is stopped when dropped
+pub struct MockServer {
+    local_addr: SocketAddr,
+    handler: Arc<CannedHandler>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// Start server on a free local port.
+    ///
+    /// * `status` - status code of each response<br/>
+    /// * `attributes` - attribute groups of each response, in addition to the charset and language
+    pub fn start(status: StatusCode, attributes: IppAttributes) -> Result<MockServer, ServerError> {
+        let handler = Arc::new(CannedHandler {
+            status,
+            attributes,
+            requests: Mutex::new(Vec::new()),
+        });
+        let server: IppServer = IppServerBuilder::new(([127, 0, 0, 1], 0))
+            .handler(handler.clone())
+            .build()
+            .wait()?;
+        let local_addr = server.local_addr();
+
+        let (shutdown, stopped) = oneshot::channel();
+        thread::spawn(move || {
+            tokio::run(server.select2(stopped).map(|_| ()).map_err(|_| ()));
+        });
+
+        Ok(MockServer {
+            local_addr,
+            handler,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Printer URI to pass to the client, for example `http://127.0.0.1:41234/`
+    pub fn uri(&self) -> String {
+        format!("http://{}/", self.local_addr)
+    }
+
+    /// Headers of the requests received so far
+    pub fn requests(&self) -> Vec<IppHeader> {
+        self.handler.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ipp_client::{IppClientBuilder, IppError};
+    use ipp_proto::{
+        attribute::{IppAttribute, JOB_ID, PRINTER_STATE},
+        ipp::{Operation, PrinterState},
+        IppOperationBuilder, IppValue,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_mock_server() {
+        let mut attributes = IppAttributes::new();
+        attributes.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE, IppValue::Enum(PrinterState::Idle as i32)),
+        );
+        let server = MockServer::start(StatusCode::SuccessfulOK, attributes).unwrap();
+        let client = IppClientBuilder::new(&server.uri()).build();
+
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let attrs = runtime
+            .block_on(client.send(IppOperationBuilder::get_printer_attributes().build()))
+            .unwrap();
+        assert_eq!(attrs.printer_state(), Some(PrinterState::Idle));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].operation_status, Operation::GetPrinterAttributes as u16);
+    }
+
+    #[test]
+    fn test_mock_server_status() {
+        let mut attributes = IppAttributes::new();
+        for id in 1..=2 {
+            attributes.begin_group(DelimiterTag::JobAttributes);
+            attributes.add_to_last_group(IppAttribute::new(JOB_ID, IppValue::Integer(id)));
+        }
+        let server = MockServer::start(StatusCode::SuccessfulOK, attributes).unwrap();
+        let client = IppClientBuilder::new(&server.uri()).build();
+
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let attrs = runtime.block_on(client.send(IppOperationBuilder::get_jobs())).unwrap();
+        assert_eq!(attrs.groups_of(DelimiterTag::JobAttributes).len(), 2);
+
+        let server = MockServer::start(StatusCode::ClientErrorNotFound, IppAttributes::new()).unwrap();
+        let client = IppClientBuilder::new(&server.uri()).build();
+        let result = runtime.block_on(client.send(IppOperationBuilder::cancel_job(7)));
+        assert!(matches!(
+            result,
+            Err(IppError::StatusError(StatusCode::ClientErrorNotFound))
+        ));
+    }
+}
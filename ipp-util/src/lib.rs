@@ -1,3 +1,3 @@
-pub use util::ipp_main;
+pub use util::{ipp_main, poll_printers};
 
 pub mod util;
@@ -4,16 +4,15 @@
 
 use std::{ffi::OsString, io, path::PathBuf};
 
-use futures::{future, Future};
+use futures::{future, stream, Future, Stream};
 use structopt::StructOpt;
-use tokio::io::AsyncRead;
 
 use ipp_client::{IppClient, IppClientBuilder, IppError};
 use ipp_proto::ipp::DelimiterTag;
-use ipp_proto::{IppAttribute, IppOperationBuilder, IppValue};
+use ipp_proto::{IppAttribute, IppJobSource, IppOperationBuilder, IppValue, PrinterCapabilities};
 
 fn new_client(uri: &str, params: &IppParams) -> IppClient {
-    IppClientBuilder::new(&uri)
+    IppClientBuilder::new(uri)
         .timeout(params.timeout)
         .ca_certs(&params.ca_certs)
         .verify_hostname(!params.no_verify_hostname)
@@ -21,18 +20,10 @@ fn new_client(uri: &str, params: &IppParams) -> IppClient {
         .build()
 }
 
-struct JobSource {
-    inner: Box<dyn AsyncRead + Send>,
-}
-
-fn new_source(cmd: &IppPrintCmd) -> Box<dyn Future<Item = JobSource, Error = io::Error> + Send + 'static> {
+fn new_source(cmd: &IppPrintCmd) -> Box<dyn Future<Item = IppJobSource, Error = io::Error> + Send + 'static> {
     match cmd.file {
-        Some(ref filename) => Box::new(
-            tokio::fs::File::open(filename.to_owned()).and_then(|file| Ok(JobSource { inner: Box::new(file) })),
-        ),
-        None => Box::new(future::ok(JobSource {
-            inner: Box::new(tokio::io::stdin()),
-        })),
+        Some(ref filename) => Box::new(IppJobSource::open(filename.to_owned())),
+        None => Box::new(future::ok(IppJobSource::from(tokio::io::stdin()))),
     }
 }
 
@@ -46,7 +37,7 @@ fn do_print(params: &IppParams, cmd: IppPrintCmd) -> Result<(), IppError> {
     }
 
     runtime.block_on(new_source(&cmd).map_err(IppError::from).and_then(move |source| {
-        let mut builder = IppOperationBuilder::print_job(source.inner);
+        let mut builder = IppOperationBuilder::print_job(source);
         if let Some(jobname) = cmd.job_name {
             builder = builder.job_title(&jobname);
         }
@@ -71,7 +62,7 @@ fn do_print(params: &IppParams, cmd: IppPrintCmd) -> Result<(), IppError> {
         }
 
         client.send(builder.build()).and_then(|attrs| {
-            if let Some(group) = attrs.groups_of(DelimiterTag::JobAttributes).get(0) {
+            if let Some(group) = attrs.groups_of(DelimiterTag::JobAttributes).first() {
                 for v in group.attributes().values() {
                     println!("{}: {}", v.name(), v.value());
                 }
@@ -82,7 +73,7 @@ fn do_print(params: &IppParams, cmd: IppPrintCmd) -> Result<(), IppError> {
 }
 
 fn do_status(params: &IppParams, cmd: IppStatusCmd) -> Result<(), IppError> {
-    let client = new_client(&cmd.uri, &params);
+    let client = new_client(&cmd.uri, params);
 
     let operation = IppOperationBuilder::get_printer_attributes()
         .attributes(&cmd.attributes)
@@ -91,7 +82,7 @@ fn do_status(params: &IppParams, cmd: IppStatusCmd) -> Result<(), IppError> {
     let mut runtime = tokio::runtime::Runtime::new().unwrap();
     let attrs = runtime.block_on(client.send(operation))?;
 
-    if let Some(group) = attrs.groups_of(DelimiterTag::PrinterAttributes).get(0) {
+    if let Some(group) = attrs.groups_of(DelimiterTag::PrinterAttributes).first() {
         let mut values: Vec<_> = group.attributes().values().collect();
         values.sort_by(|a, b| a.name().cmp(b.name()));
         for v in values {
@@ -101,6 +92,31 @@ fn do_status(params: &IppParams, cmd: IppStatusCmd) -> Result<(), IppError> {
     Ok(())
 }
 
+/// Get capabilities of multiple printers concurrently
+///
+/// * `uris` - printer URIs to query<br/>
+/// * `concurrency` - maximum number of requests in flight<br/>
+/// * `builder` - creates the client builder for each URI, for example to set timeouts or credentials<br/>
+///
+/// Results are yielded in completion order together with the printer URI. A failure to query one printer
+/// is reported in its own result item and does not terminate the stream, so the stream itself never fails.
+pub fn poll_printers<I, F>(
+    uris: I,
+    concurrency: usize,
+    builder: F,
+) -> impl Stream<Item = (String, Result<PrinterCapabilities, IppError>), Error = IppError>
+where
+    I: IntoIterator<Item = String>,
+    F: Fn(&str) -> IppClientBuilder,
+{
+    stream::iter_ok(uris)
+        .map(move |uri| {
+            let client = builder(&uri).build();
+            client.capabilities().then(move |result| Ok((uri, result)))
+        })
+        .buffer_unordered(concurrency.max(1))
+}
+
 #[derive(StructOpt)]
 #[structopt(name = "IPP print utility", about = "", author = "", rename_all = "kebab-case")]
 struct IppParams {
@@ -250,3 +266,57 @@ where
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use ipp_proto::{
+        attribute::PRINTER_STATE,
+        ipp::{PrinterState, StatusCode},
+        IppAttributes,
+    };
+    use ipp_server::testing::MockServer;
+
+    use super::*;
+
+    #[test]
+    fn test_poll_printers_isolates_failures() {
+        let uris = vec!["http://127.0.0.1:1/printers/foo".to_owned(), "foo".to_owned()];
+
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let mut results = runtime
+            .block_on(poll_printers(uris, 2, IppClientBuilder::new).collect())
+            .unwrap();
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "foo");
+        assert!(matches!(results[0].1, Err(IppError::ParamError(_))));
+        assert_eq!(results[1].0, "http://127.0.0.1:1/printers/foo");
+        assert!(results[1].1.is_err());
+    }
+
+    #[test]
+    fn test_poll_printers_builder() {
+        let mut attributes = IppAttributes::new();
+        attributes.add(
+            DelimiterTag::PrinterAttributes,
+            IppAttribute::new(PRINTER_STATE, IppValue::Enum(PrinterState::Idle as i32)),
+        );
+        let server = MockServer::start(StatusCode::SuccessfulOK, attributes).unwrap();
+
+        // the builder decides where each printer is reached
+        let uri = server.uri();
+        let uris = vec!["printer-a".to_owned(), "printer-b".to_owned()];
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let results = runtime
+            .block_on(poll_printers(uris, 2, move |_| IppClientBuilder::new(&uri).timeout(5)).collect())
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(_, result)| result
+            .as_ref()
+            .map(|c| c.attribute(PRINTER_STATE).is_some())
+            .unwrap_or(false)));
+        assert_eq!(server.requests().len(), 2);
+    }
+}
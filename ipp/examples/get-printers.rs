@@ -1,13 +1,8 @@
 use std::{env, error::Error, process::exit};
 
-use num_traits::cast::FromPrimitive;
-
 use ipp::{
-    client::{IppClientBuilder, IppError},
-    proto::{
-        ipp::{DelimiterTag, PrinterState},
-        operation::cups::CupsGetPrinters,
-    },
+    client::IppClientBuilder,
+    proto::{ipp::DelimiterTag, model::PrinterAttributes, operation::cups::CupsGetPrinters},
 };
 
 pub fn main() -> Result<(), Box<dyn Error>> {
@@ -29,13 +24,12 @@ pub fn main() -> Result<(), Box<dyn Error>> {
     for group in attrs.groups_of(DelimiterTag::PrinterAttributes) {
         let name = group.attributes()["printer-name"].value();
         let uri = group.attributes()["device-uri"].value();
-        let state = group.attributes()["printer-state"]
-            .value()
-            .as_enum()
-            .and_then(|v| PrinterState::from_i32(*v))
-            .ok_or(IppError::InvalidAttributeType)?;
+        let state = PrinterAttributes::new(group)
+            .state()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "unknown".to_owned());
 
-        println!("{}: {} {:?}", name, uri, state);
+        println!("{}: {} {}", name, uri, state);
     }
 
     Ok(())
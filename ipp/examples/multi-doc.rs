@@ -41,7 +41,7 @@ pub fn main() -> Result<(), Box<dyn Error>> {
 
     let ops_attr = printer_attrs
         .groups_of(DelimiterTag::PrinterAttributes)
-        .get(0)
+        .first()
         .and_then(|g| g.attributes().get(OPERATIONS_SUPPORTED))
         .ok_or(IppError::MissingAttribute)?;
 
@@ -54,7 +54,7 @@ pub fn main() -> Result<(), Box<dyn Error>> {
     let attrs = runtime.block_on(client.send(create_op))?;
     let job_id = *attrs
         .groups_of(DelimiterTag::JobAttributes)
-        .get(0)
+        .first()
         .and_then(|g| g.attributes().get(JOB_ID))
         .and_then(|attr| attr.value().as_integer())
         .ok_or(IppError::MissingAttribute)?;
@@ -62,7 +62,8 @@ pub fn main() -> Result<(), Box<dyn Error>> {
     println!("job id: {}", job_id);
 
     for (i, item) in args.iter().enumerate().skip(2) {
-        let client = IppClientBuilder::new(&uri).build();
+        // clones share the connection to the printer
+        let client = client.clone();
 
         let last = i >= (args.len() - 1);
         println!("Sending {}, last: {}", item, last);
@@ -45,7 +45,7 @@
 
 pub use ipp_proto as proto;
 
-#[cfg(feature = "client")]
+#[cfg(any(feature = "client", feature = "blocking"))]
 pub use ipp_client as client;
 
 #[cfg(feature = "server")]